@@ -1,13 +1,17 @@
-use failure::Error;
-use graph::{Delaunay, Voronoi};
+use failure::{format_err, Error};
+use graph::cloud::PointReader;
+use graph::{Delaunay, Point, Voronoi};
 use image::ToImage;
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fs::File;
+use std::io::BufReader;
 use std::iter::FromIterator;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub struct Application {
     seed: u64,
     number: u64,
+    input: Option<PathBuf>,
 }
 
 impl Application {
@@ -15,25 +19,78 @@ impl Application {
         Ok(Application {
             seed: settings.seed(),
             number: settings.number(),
+            input: settings.input().map(Path::to_path_buf),
         })
     }
 
+    // Reads `.xyz`, `.txt` and `.pts` files as XYZ and anything else as CSV, then scales the
+    // points into the unit square that is drawn.
+    fn read(path: &Path) -> Result<Delaunay, Error> {
+        let reader = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("xyz") | Some("txt") | Some("pts") => PointReader::xyz(),
+            _ => PointReader::csv(),
+        };
+        let mut cloud = reader.read(BufReader::new(File::open(path)?))?;
+        log::info!(
+            "Read {} points with columns {:?}",
+            cloud.points.len(),
+            cloud
+                .columns
+                .iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        );
+
+        let (mut x0, mut y0, mut x1, mut y1) = (
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        );
+        for p in &cloud.points {
+            x0 = x0.min(p.x);
+            y0 = y0.min(p.y);
+            x1 = x1.max(p.x);
+            y1 = y1.max(p.y);
+        }
+        let scale = (x1 - x0).max(y1 - y0);
+        if scale > 0.0 {
+            for p in &mut cloud.points {
+                *p = Point::new((p.x - x0) / scale, (p.y - y0) / scale);
+            }
+        }
+
+        cloud
+            .delaunay()
+            .map_err(|()| format_err!("Triangulation of {} failed", path.display()))
+    }
+
     pub fn run(self) -> Result<(), Error> {
         log::debug!("seed: {}", self.seed);
         log::debug!("number: {}", self.number);
+        log::debug!("input: {:?}", self.input);
 
-        let mut rng = StdRng::seed_from_u64(self.seed);
-        let delaunay = loop {
-            match Result::<Delaunay, _>::from_iter((0..self.number).map(|_| rng.gen())) {
-                Ok(g) => break g,
-                Err(e) => log::warn!("Triangulation failed ({:?}). Trying again.", e),
+        let delaunay = match &self.input {
+            Some(path) => Self::read(path)?,
+            None => {
+                let mut rng = StdRng::seed_from_u64(self.seed);
+                loop {
+                    match Result::<Delaunay, _>::from_iter((0..self.number).map(|_| rng.gen())) {
+                        Ok(g) => break g,
+                        Err(e) => log::warn!("Triangulation failed ({:?}). Trying again.", e),
+                    }
+                }
             }
         };
         let voronoi = Voronoi::from(&delaunay);
 
+        let report = delaunay.quality_report();
+        log::info!("Mesh quality\n{}", report);
+
         log::info!("Writing to file");
         let path = Path::new("./output.png");
         (&*delaunay, &*voronoi).to_image(1024, 1024).save(path)?;
+        serde_json::to_writer_pretty(File::create(path.with_extension("json"))?, &report)?;
 
         //(&*delaunay, &*voronoi).to_image(16384, 16384).save(path)?;
 
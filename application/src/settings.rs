@@ -1,4 +1,5 @@
 pub trait Settings {
     fn seed(&self) -> u64;
     fn number(&self) -> u64;
+    fn input(&self) -> Option<&std::path::Path>;
 }
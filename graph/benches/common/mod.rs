@@ -0,0 +1,165 @@
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::{BenchmarkId, Criterion, SamplingMode, Throughput};
+use graph::distributions::{Circle, GaussianClusters, Halton, JitteredGrid, NearCollinear, Sobol};
+use graph::Point;
+use rand::distributions::{Distribution, Standard};
+use rand::{rngs::StdRng, SeedableRng};
+use std::env;
+use std::time::Duration;
+
+pub const DISTRIBUTIONS: &[&str] = &[
+    "uniform",
+    "jittered-grid",
+    "halton",
+    "sobol",
+    "clusters",
+    "circle",
+    "near-collinear",
+];
+
+/// Distribution picked with `BENCH_DISTRIBUTION`, uniform if unset. Panics on any name not
+/// in `DISTRIBUTIONS`, so that a typo does not quietly benchmark uniform points.
+pub fn distribution() -> &'static str {
+    let name = match env::var("BENCH_DISTRIBUTION") {
+        Ok(name) => name,
+        Err(_) => return DISTRIBUTIONS[0],
+    };
+    DISTRIBUTIONS
+        .iter()
+        .find(|&&d| d == name)
+        .copied()
+        .unwrap_or_else(|| {
+            panic!(
+                "unknown BENCH_DISTRIBUTION `{}`, expected one of: {}",
+                name,
+                DISTRIBUTIONS.join(", ")
+            )
+        })
+}
+
+pub fn points(distribution: &str, count: usize) -> Vec<Point> {
+    let mut rng = StdRng::seed_from_u64(123456);
+    let side = (count as f64).sqrt().ceil() as usize;
+
+    match distribution {
+        "uniform" => sample(Standard, &mut rng, count),
+        "jittered-grid" => JitteredGrid::new(side, side, 0.5)
+            .points(&mut rng)
+            .take(count)
+            .collect(),
+        "halton" => Halton::new().take(count).collect(),
+        "sobol" => Sobol::new().take(count).collect(),
+        "clusters" => {
+            let centers = sample(Standard, &mut rng, 8);
+            sample(GaussianClusters::new(centers, 0.02), &mut rng, count)
+        }
+        "circle" => sample(Circle::new(Point::new(0.5, 0.5), 0.5), &mut rng, count),
+        "near-collinear" => sample(
+            NearCollinear::new(Point::new(0.0, 0.0), Point::new(1.0, 1.0), 1e-6),
+            &mut rng,
+            count,
+        ),
+        name => panic!(
+            "unknown distribution `{}`, expected one of: {}",
+            name,
+            DISTRIBUTIONS.join(", ")
+        ),
+    }
+}
+
+fn sample(distribution: impl Distribution<Point>, rng: &mut StdRng, count: usize) -> Vec<Point> {
+    distribution.sample_iter(rng).take(count).collect()
+}
+
+/// Criterion measurement in bytes, fed through `iter_custom` by [`memory`] rather than timed.
+pub struct Bytes;
+
+impl Measurement for Bytes {
+    type Intermediate = ();
+    type Value = f64;
+
+    fn start(&self) {}
+
+    fn end(&self, _: ()) -> f64 {
+        0.0
+    }
+
+    fn add(&self, v1: &f64, v2: &f64) -> f64 {
+        v1 + v2
+    }
+
+    fn zero(&self) -> f64 {
+        0.0
+    }
+
+    fn to_f64(&self, value: &f64) -> f64 {
+        *value
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        self
+    }
+}
+
+impl ValueFormatter for Bytes {
+    fn scale_values(&self, typical: f64, values: &mut [f64]) -> &'static str {
+        let (denominator, unit) = if typical < 1024.0 {
+            (1.0, "B")
+        } else if typical < 1024.0 * 1024.0 {
+            (1024.0, "KiB")
+        } else {
+            (1024.0 * 1024.0, "MiB")
+        };
+        for value in values {
+            *value /= denominator;
+        }
+        unit
+    }
+
+    fn scale_throughputs(&self, _: f64, _: &Throughput, _: &mut [f64]) -> &'static str {
+        "B"
+    }
+
+    fn scale_for_machines(&self, _: &mut [f64]) -> &'static str {
+        "B"
+    }
+}
+
+/// Criterion configured from the command line for [`memory`]. Every sample reads the same
+/// number, so there is nothing to warm up, and plots are turned off after the arguments are
+/// read since plotting a zero-width distribution panics.
+pub fn bytes() -> Criterion<Bytes> {
+    Criterion::default()
+        .with_measurement(Bytes)
+        .warm_up_time(Duration::from_nanos(1))
+        .measurement_time(Duration::from_millis(1))
+        .sample_size(10)
+        .configure_from_args()
+        .without_plots()
+}
+
+/// Reports `usage(count)` for every count as the `memory` benchmark of `name`, split by the
+/// index width of the build so that `--features compact-index` runs sit next to default ones.
+pub fn memory(
+    c: &mut Criterion<Bytes>,
+    name: &str,
+    counts: &[usize],
+    usage: impl Fn(&[Point]) -> usize,
+) {
+    let build = if cfg!(feature = "compact-index") {
+        "compact"
+    } else {
+        "default"
+    };
+    let mut group = c.benchmark_group(format!("{}/{}/memory/{}", name, distribution(), build));
+    group.sampling_mode(SamplingMode::Flat);
+
+    for &count in counts {
+        let bytes = usage(&points(distribution(), count)) as f64;
+        group.bench_function(BenchmarkId::from_parameter(count), |b| {
+            b.iter_custom(|iters| bytes * iters as f64)
+        });
+    }
+
+    group.finish();
+}
@@ -1,37 +1,37 @@
 #[macro_use]
 extern crate criterion;
 
-use criterion::{AxisScale, Criterion, ParameterizedBenchmark, PlotConfiguration};
-use graph::{Delaunay, Point};
-use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::iter::repeat_with;
+use criterion::{AxisScale, BenchmarkId, Criterion, PlotConfiguration};
+use graph::Delaunay;
+
+mod common;
 
 const COUNTS: &[usize] = &[100, 1000, 10_000, 100_000, 1_000_000];
 
 fn bench(c: &mut Criterion) {
-    let mut rng = StdRng::seed_from_u64(123456);
+    let distribution = common::distribution();
+
+    let mut group = c.benchmark_group(format!("delaunay/{}", distribution));
+    group
+        .sample_size(20)
+        .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
 
-    let all_points: Vec<_> = repeat_with(|| rng.gen())
-        .map(|(x, y)| Point::new(x, y))
-        .take(*COUNTS.last().unwrap())
-        .collect();
+    for count in COUNTS {
+        let points = common::points(distribution, *count);
 
-    let bench = ParameterizedBenchmark::new(
-        "delaunay",
-        move |b, &&count| {
-            let points = all_points[..count].to_vec();
+        group.bench_with_input(BenchmarkId::from_parameter(count), &points, |b, points| {
             b.iter(|| Delaunay::from(points.clone()))
-        },
-        COUNTS,
-    );
-
-    c.bench(
-        "delaunay",
-        bench
-            .sample_size(20)
-            .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic)),
-    );
+        });
+    }
+
+    group.finish();
+}
+
+fn sizes() {
+    common::memory(&mut common::bytes(), "delaunay", COUNTS, |points| {
+        Delaunay::from(points.to_vec()).unwrap().memory_usage()
+    });
 }
 
-criterion::criterion_group!(benches, bench);
-criterion::criterion_main!(benches);
+criterion_group!(benches, bench);
+criterion_main!(benches, sizes);
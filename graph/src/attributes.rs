@@ -0,0 +1,196 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+pub trait Attribute: Any + Clone + Debug + Default + Send + Sync {}
+
+impl<A: Any + Clone + Debug + Default + Send + Sync> Attribute for A {}
+
+trait Column: Debug + Send + Sync {
+    fn push_default(&mut self);
+    fn push_copy(&mut self, from: usize);
+    fn swap_remove(&mut self, index: usize);
+    fn select(&self, indices: &[usize], len: usize) -> Box<dyn Column>;
+    fn boxed_clone(&self) -> Box<dyn Column>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<A: Attribute> Column for Vec<A> {
+    #[inline]
+    fn push_default(&mut self) {
+        self.push(A::default());
+    }
+
+    #[inline]
+    fn push_copy(&mut self, from: usize) {
+        let value = self[from].clone();
+        self.push(value);
+    }
+
+    #[inline]
+    fn swap_remove(&mut self, index: usize) {
+        Vec::swap_remove(self, index);
+    }
+
+    #[inline]
+    fn select(&self, indices: &[usize], len: usize) -> Box<dyn Column> {
+        let mut values = indices
+            .iter()
+            .map(|&index| self[index].clone())
+            .collect::<Vec<_>>();
+        values.resize_with(len, A::default);
+        Box::new(values)
+    }
+
+    #[inline]
+    fn boxed_clone(&self) -> Box<dyn Column> {
+        Box::new(self.clone())
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct Attributes {
+    len: usize,
+    columns: HashMap<String, Box<dyn Column>>,
+}
+
+impl Clone for Attributes {
+    fn clone(&self) -> Self {
+        Attributes {
+            len: self.len,
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, column)| (name.clone(), column.boxed_clone()))
+                .collect(),
+        }
+    }
+}
+
+impl Attributes {
+    #[inline]
+    pub(crate) fn new(len: usize) -> Self {
+        Attributes {
+            len,
+            columns: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.columns.keys().map(String::as_str)
+    }
+
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.columns.contains_key(name)
+    }
+
+    /// Adds a column filled with `A::default()`, replacing any column of the same name.
+    #[inline]
+    pub fn add<A: Attribute>(&mut self, name: &str) -> &mut [A] {
+        let mut values = Vec::new();
+        values.resize_with(self.len, A::default);
+        self.columns.insert(name.to_owned(), Box::new(values));
+        self.get_mut(name).unwrap()
+    }
+
+    /// Adds a column, handing `values` back if it does not hold one value per element.
+    #[inline]
+    pub fn insert<A: Attribute>(&mut self, name: &str, values: Vec<A>) -> Result<(), Vec<A>> {
+        if values.len() == self.len {
+            self.columns.insert(name.to_owned(), Box::new(values));
+            Ok(())
+        } else {
+            Err(values)
+        }
+    }
+
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.columns.remove(name).is_some()
+    }
+
+    #[inline]
+    pub fn get<A: Attribute>(&self, name: &str) -> Option<&[A]> {
+        self.columns
+            .get(name)
+            .and_then(|column| column.as_any().downcast_ref::<Vec<A>>())
+            .map(Vec::as_slice)
+    }
+
+    #[inline]
+    pub fn get_mut<A: Attribute>(&mut self, name: &str) -> Option<&mut [A]> {
+        self.columns
+            .get_mut(name)
+            .and_then(|column| column.as_any_mut().downcast_mut::<Vec<A>>())
+            .map(Vec::as_mut_slice)
+    }
+
+    #[inline]
+    pub(crate) fn push_default(&mut self) {
+        self.len += 1;
+        self.columns
+            .values_mut()
+            .for_each(|column| column.push_default());
+    }
+
+    #[inline]
+    pub(crate) fn push_copy(&mut self, from: usize) {
+        self.len += 1;
+        self.columns
+            .values_mut()
+            .for_each(|column| column.push_copy(from));
+    }
+
+    #[inline]
+    pub(crate) fn swap_remove(&mut self, index: usize) {
+        self.len -= 1;
+        self.columns
+            .values_mut()
+            .for_each(|column| column.swap_remove(index));
+    }
+
+    // Columns as `Vec<A>` behind `Any`, for serializers that recognize their type.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub(crate) fn columns(&self) -> impl Iterator<Item = (&str, &dyn Any)> {
+        self.columns
+            .iter()
+            .map(|(name, column)| (name.as_str(), column.as_any()))
+    }
+
+    // Picks `indices` as the first elements of a new storage of `len` elements.
+    #[inline]
+    pub(crate) fn select(&self, indices: &[usize], len: usize) -> Self {
+        Attributes {
+            len,
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, column)| (name.clone(), column.select(indices, len)))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,771 @@
+//! Versioned little-endian binary format holding the point, half-edge, face and vertex
+//! arrays as they are stored in memory.
+//!
+//! Layout: the magic `TRIG`, a `u16` version, the byte widths of scalars (4 or 8) and indices
+//! (4 or 8), the point, half-edge, face and vertex counts as `u64`, then the arrays (points as
+//! `x y`, half-edges as `vertex next face`, faces as `edge`, vertices as `edge point` with all
+//! bits set for the infinite vertex) and a 64-bit checksum of everything before it.
+//! Attributes are not stored.
+//!
+//! A [`GraphView`] walks the file in place through [`EdgeView`], [`FaceView`] and
+//! [`VertexView`] handles, which navigate like [`Edge`](crate::Edge), [`Face`](crate::Face)
+//! and [`Vertex`](crate::Vertex). Attributes and the geometry helpers of the owned handles
+//! need [`GraphView::to_graph`] first.
+
+use crate::graph::{fits_index, Graph, GraphEdge, GraphFace, GraphVertex};
+use crate::validate::{triangulation, validate, Records};
+use crate::{Delaunay, EdgeId, FaceId, Float, HalfEdgeId, Point, ValidationError, VertexId};
+use either::{Either, Left, Right};
+use std::convert::TryFrom;
+use std::io::{self, BufWriter, Read, Write};
+use std::iter::successors;
+use std::marker::PhantomData;
+
+const MAGIC: &[u8; 4] = b"TRIG";
+const VERSION: u16 = 1;
+const HEADER: usize = 40;
+
+#[inline]
+fn invalid(message: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+// FNV-1a over little-endian 64-bit words, the last one zero-padded.
+#[derive(Clone, Copy)]
+struct Checksum {
+    hash: u64,
+    pending: [u8; 8],
+    len: usize,
+}
+
+impl Checksum {
+    #[inline]
+    fn new() -> Self {
+        Checksum {
+            hash: 0xcbf2_9ce4_8422_2325,
+            pending: [0; 8],
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn mix(&mut self, word: u64) {
+        self.hash = (self.hash ^ word).wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    fn update(&mut self, mut bytes: &[u8]) {
+        while self.len > 0 && !bytes.is_empty() {
+            self.pending[self.len] = bytes[0];
+            self.len = (self.len + 1) % 8;
+            bytes = &bytes[1..];
+            if self.len == 0 {
+                self.mix(u64::from_le_bytes(self.pending));
+            }
+        }
+
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            let mut buffer = [0; 8];
+            buffer.copy_from_slice(word);
+            self.mix(u64::from_le_bytes(buffer));
+        }
+        for &byte in words.remainder() {
+            self.pending[self.len] = byte;
+            self.len += 1;
+        }
+    }
+
+    #[inline]
+    fn finish(mut self) -> u64 {
+        if self.len > 0 {
+            self.pending[self.len..]
+                .iter_mut()
+                .for_each(|byte| *byte = 0);
+            self.mix(u64::from_le_bytes(self.pending));
+        }
+        self.hash
+    }
+}
+
+struct Writer<W: Write> {
+    writer: W,
+    checksum: Checksum,
+}
+
+impl<W: Write> Writer<W> {
+    #[inline]
+    fn bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.checksum.update(bytes);
+        self.writer.write_all(bytes)
+    }
+
+    #[inline]
+    fn uint(&mut self, value: u64, width: usize) -> io::Result<()> {
+        self.bytes(&value.to_le_bytes()[..width])
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Header {
+    scalar: usize,
+    index: usize,
+    points: usize,
+    edges: usize,
+    faces: usize,
+    vertices: usize,
+}
+
+impl Header {
+    fn parse(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() < HEADER || &bytes[..4] != MAGIC {
+            return Err(invalid("not a graph file"));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != VERSION {
+            return Err(invalid(format!("unsupported version {}", version)));
+        }
+
+        let (scalar, index) = (usize::from(bytes[6]), usize::from(bytes[7]));
+        if (scalar != 4 && scalar != 8) || (index != 4 && index != 8) {
+            return Err(invalid("unsupported scalar or index width"));
+        }
+        let count = |i: usize| {
+            let value = read(bytes, HEADER - 32 + 8 * i, 8);
+            usize::try_from(value).map_err(|_| invalid("count out of range"))
+        };
+
+        let header = Header {
+            scalar,
+            index,
+            points: count(0)?,
+            edges: count(1)?,
+            faces: count(2)?,
+            vertices: count(3)?,
+        };
+        match header.len() {
+            Some(len) if len == bytes.len() => Ok(header),
+            _ => Err(invalid("file size does not match its header")),
+        }
+    }
+
+    // Start of each array, and the total file size including the checksum.
+    #[inline]
+    fn offsets(&self) -> Option<[usize; 5]> {
+        let points = HEADER;
+        let edges = points.checked_add(self.points.checked_mul(2 * self.scalar)?)?;
+        let faces = edges.checked_add(self.edges.checked_mul(3 * self.index)?)?;
+        let vertices = faces.checked_add(self.faces.checked_mul(self.index)?)?;
+        let end = vertices.checked_add(self.vertices.checked_mul(2 * self.index)?)?;
+        Some([points, edges, faces, vertices, end])
+    }
+
+    #[inline]
+    fn len(&self) -> Option<usize> {
+        self.offsets()?[4].checked_add(8)
+    }
+}
+
+#[inline]
+fn read(bytes: &[u8], offset: usize, width: usize) -> u64 {
+    let mut buffer = [0; 8];
+    buffer[..width].copy_from_slice(&bytes[offset..offset + width]);
+    u64::from_le_bytes(buffer)
+}
+
+/// Read-only graph over the bytes of a binary file, decoding elements as they are accessed.
+/// Its [`EdgeView`], [`FaceView`] and [`VertexView`] handles mirror those of a [`Graph`] without
+/// attributes or geometry.
+#[derive(Clone, Copy)]
+pub struct GraphView<'a, S: Float = f64> {
+    bytes: &'a [u8],
+    header: Header,
+    offsets: [usize; 5],
+    scalar: PhantomData<S>,
+}
+
+impl<'a, S: Float> GraphView<'a, S> {
+    /// Checks the header and the checksum of `bytes` and then every half-edge invariant like
+    /// [`Graph::validate`], so that walking the view can neither index out of range nor loop.
+    pub fn new(bytes: &'a [u8]) -> io::Result<Self> {
+        let view = GraphView::unchecked(bytes)?;
+
+        let end = view.offsets[4];
+        let mut checksum = Checksum::new();
+        checksum.update(&bytes[..end]);
+        if checksum.finish() != read(bytes, end, 8) {
+            return Err(invalid("checksum mismatch"));
+        }
+
+        view.check_ranges().map_err(invalid)?;
+        validate(&view).map_err(invalid)?;
+        Ok(view)
+    }
+
+    // Raw indices must be in range before they go through `GraphEdge`, whose compact indices
+    // would not hold larger values.
+    fn check_ranges(&self) -> Result<(), ValidationError> {
+        let Header {
+            points,
+            edges,
+            faces,
+            vertices,
+            ..
+        } = self.header;
+        let raw = |array, element, field, fields| self.raw(array, element, field, fields);
+        let below = |value: u64, bound: usize| value < bound as u64;
+
+        for id in 0..edges {
+            if !below(raw(1, id, 0, 3), vertices)
+                || !below(raw(1, id, 1, 3), edges)
+                || !below(raw(1, id, 2, 3), faces)
+            {
+                return Err(ValidationError::DanglingEdge(HalfEdgeId::new(id)));
+            }
+        }
+        for id in 0..faces {
+            if !below(raw(2, id, 0, 1), edges) {
+                return Err(ValidationError::BrokenFace(FaceId::new(id)));
+            }
+        }
+        let infinite = u64::MAX >> (64 - 8 * self.header.index);
+        for id in 0..vertices {
+            let point = raw(3, id, 1, 2);
+            if !below(raw(3, id, 0, 2), edges) || (point != infinite && !below(point, points)) {
+                return Err(ValidationError::BrokenVertex(VertexId::new(id)));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Header checks only, for bytes that passed `new` before.
+    #[inline]
+    fn unchecked(bytes: &'a [u8]) -> io::Result<Self> {
+        let header = Header::parse(bytes)?;
+        if !fits_index(
+            header
+                .points
+                .max(header.edges)
+                .max(header.faces)
+                .max(header.vertices),
+        ) {
+            return Err(invalid("too many elements for compact indices"));
+        }
+        Ok(GraphView {
+            bytes,
+            header,
+            offsets: header.offsets().unwrap(),
+            scalar: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn raw(&self, array: usize, element: usize, field: usize, fields: usize) -> u64 {
+        let width = self.header.index;
+        let offset = self.offsets[array] + (element * fields + field) * width;
+        read(self.bytes, offset, width)
+    }
+
+    #[inline]
+    fn index(&self, array: usize, element: usize, field: usize, fields: usize) -> usize {
+        self.raw(array, element, field, fields) as usize
+    }
+
+    #[inline]
+    fn scalar(&self, offset: usize) -> S {
+        if self.header.scalar == 4 {
+            S::from_f64(f64::from(
+                f32::from_bits(read(self.bytes, offset, 4) as u32),
+            ))
+        } else {
+            S::from_f64(f64::from_bits(read(self.bytes, offset, 8)))
+        }
+    }
+
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.header.edges / 2
+    }
+
+    #[inline]
+    pub fn face_count(&self) -> usize {
+        self.header.faces
+    }
+
+    #[inline]
+    pub fn vertex_count(&self) -> usize {
+        self.header.vertices
+    }
+
+    /// Position of `vertex`, `None` for the infinite vertex.
+    #[inline]
+    pub fn position(&self, vertex: VertexId) -> Option<Point<S>> {
+        self.vertex_point(vertex.index()).map(|point| {
+            let offset = self.offsets[0] + point * 2 * self.header.scalar;
+            Point::new(
+                self.scalar(offset),
+                self.scalar(offset + self.header.scalar),
+            )
+        })
+    }
+
+    #[inline]
+    pub fn origin(&self, edge: HalfEdgeId) -> VertexId {
+        VertexId::new(self.origin_of(edge.index()))
+    }
+
+    #[inline]
+    pub fn next(&self, edge: HalfEdgeId) -> HalfEdgeId {
+        HalfEdgeId::new(self.next_of(edge.index()))
+    }
+
+    #[inline]
+    pub fn left_face(&self, edge: HalfEdgeId) -> FaceId {
+        FaceId::new(self.left_of(edge.index()))
+    }
+
+    /// Some half-edge with `face` on its left.
+    #[inline]
+    pub fn face_edge(&self, face: FaceId) -> HalfEdgeId {
+        HalfEdgeId::new(Records::face_edge(self, face.index()))
+    }
+
+    /// Corners of `face` in counter-clockwise order.
+    #[inline]
+    pub fn face_vertices(&self, face: FaceId) -> impl Iterator<Item = VertexId> + '_ {
+        let first = self.face_edge(face);
+        successors(Some(first), move |&edge| {
+            Some(self.next(edge)).filter(|&next| next != first)
+        })
+        .map(move |edge| self.origin(edge))
+    }
+
+    #[inline]
+    pub fn edge(&self, id: EdgeId) -> EdgeView<'_, S> {
+        self.edge_at(id.half_edges().0.index())
+    }
+
+    #[inline]
+    pub fn half_edge(&self, id: HalfEdgeId) -> EdgeView<'_, S> {
+        self.edge_at(id.index())
+    }
+
+    #[inline]
+    pub fn face(&self, id: FaceId) -> FaceView<'_, S> {
+        self.face_at(id.index())
+    }
+
+    #[inline]
+    pub fn vertex(&self, id: VertexId) -> VertexView<'_, S> {
+        self.vertex_at(id.index())
+    }
+
+    #[inline]
+    pub fn edges(&self) -> impl Iterator<Item = EdgeView<'_, S>> {
+        (0..self.edge_count()).map(move |id| self.edge_at(id << 1))
+    }
+
+    #[inline]
+    pub fn faces(&self) -> impl Iterator<Item = FaceView<'_, S>> {
+        (0..self.face_count()).map(move |id| self.face_at(id))
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> impl Iterator<Item = VertexView<'_, S>> {
+        (0..self.vertex_count()).map(move |id| self.vertex_at(id))
+    }
+
+    #[inline]
+    fn edge_at(&self, id: usize) -> EdgeView<'_, S> {
+        EdgeView { view: self, id }
+    }
+
+    #[inline]
+    fn face_at(&self, id: usize) -> FaceView<'_, S> {
+        FaceView { view: self, id }
+    }
+
+    #[inline]
+    fn vertex_at(&self, id: usize) -> VertexView<'_, S> {
+        VertexView { view: self, id }
+    }
+
+    /// Copies the arrays into an owned graph.
+    pub fn to_graph(&self) -> Graph<(), S> {
+        let points = (0..self.header.points)
+            .map(|point| {
+                let offset = self.offsets[0] + point * 2 * self.header.scalar;
+                Point::new(
+                    self.scalar(offset),
+                    self.scalar(offset + self.header.scalar),
+                )
+            })
+            .collect();
+        let edges = (0..self.header.edges)
+            .map(|edge| Records::half_edge(self, edge))
+            .collect();
+        let faces = (0..self.header.faces)
+            .map(|face| GraphFace::new(Records::face_edge(self, face)))
+            .collect();
+        let vertices = (0..self.header.vertices)
+            .map(|vertex| {
+                let position = match self.vertex_point(vertex) {
+                    Some(point) => Left(point),
+                    None => Right(()),
+                };
+                GraphVertex::new(self.vertex_edge(vertex), position)
+            })
+            .collect();
+
+        Graph::new(points, edges, faces, vertices)
+    }
+}
+
+/// Half-edge of a [`GraphView`], navigating like [`Edge`](crate::Edge).
+#[derive(Clone, Copy)]
+pub struct EdgeView<'a, S: Float = f64> {
+    view: &'a GraphView<'a, S>,
+    id: usize,
+}
+
+impl<'a, S: Float> EdgeView<'a, S> {
+    #[inline]
+    pub fn id(&self) -> EdgeId {
+        EdgeId::new(self.id >> 1)
+    }
+
+    #[inline]
+    pub fn half_edge_id(&self) -> HalfEdgeId {
+        HalfEdgeId::new(self.id)
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> (VertexView<'a, S>, VertexView<'a, S>) {
+        (self.origin(), self.destination())
+    }
+
+    #[inline]
+    pub fn origin(&self) -> VertexView<'a, S> {
+        self.view.vertex_at(self.view.origin_of(self.id))
+    }
+
+    #[inline]
+    pub fn destination(&self) -> VertexView<'a, S> {
+        self.view.vertex_at(self.view.origin_of(self.id ^ 1))
+    }
+
+    #[inline]
+    pub fn twin(&self) -> EdgeView<'a, S> {
+        self.view.edge_at(self.id ^ 1)
+    }
+
+    #[inline]
+    pub fn next(&self) -> EdgeView<'a, S> {
+        self.view.edge_at(self.view.next_of(self.id))
+    }
+
+    #[inline]
+    pub fn prev(&self) -> EdgeView<'a, S> {
+        let mut current = *self;
+        loop {
+            let next = current.next();
+            if next.id == self.id {
+                break current;
+            }
+            current = next;
+        }
+    }
+
+    #[inline]
+    pub fn left_face(&self) -> FaceView<'a, S> {
+        self.view.face_at(self.view.left_of(self.id))
+    }
+
+    #[inline]
+    pub fn right_face(&self) -> FaceView<'a, S> {
+        self.view.face_at(self.view.left_of(self.id ^ 1))
+    }
+
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        self.origin().is_infinite() || self.destination().is_infinite()
+    }
+}
+
+/// Face of a [`GraphView`], navigating like [`Face`](crate::Face).
+#[derive(Clone, Copy)]
+pub struct FaceView<'a, S: Float = f64> {
+    view: &'a GraphView<'a, S>,
+    id: usize,
+}
+
+impl<'a, S: Float> FaceView<'a, S> {
+    #[inline]
+    pub fn id(&self) -> FaceId {
+        FaceId::new(self.id)
+    }
+
+    #[inline]
+    pub fn edges(&self) -> impl Iterator<Item = EdgeView<'a, S>> {
+        let first = self.view.edge_at(Records::face_edge(self.view, self.id));
+
+        successors(Some(first), move |e| {
+            Some(e.next()).filter(|n| n.id != first.id)
+        })
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> impl Iterator<Item = VertexView<'a, S>> {
+        self.edges().map(|edge| edge.origin())
+    }
+
+    #[inline]
+    pub fn neighbors(&self) -> impl Iterator<Item = FaceView<'a, S>> {
+        self.edges().map(|edge| edge.right_face())
+    }
+
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        self.vertices().any(|vertex| vertex.is_infinite())
+    }
+}
+
+/// Vertex of a [`GraphView`], navigating like [`Vertex`](crate::Vertex).
+#[derive(Clone, Copy)]
+pub struct VertexView<'a, S: Float = f64> {
+    view: &'a GraphView<'a, S>,
+    id: usize,
+}
+
+impl<'a, S: Float> VertexView<'a, S> {
+    #[inline]
+    pub fn id(&self) -> VertexId {
+        VertexId::new(self.id)
+    }
+
+    #[inline]
+    pub fn position(&self) -> Either<Point<S>, ()> {
+        match self.view.position(self.id()) {
+            Some(point) => Left(point),
+            None => Right(()),
+        }
+    }
+
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        self.view.vertex_point(self.id).is_none()
+    }
+
+    /// Edges leaving this vertex, in clockwise order.
+    #[inline]
+    pub fn outgoing_edges(&self) -> impl Iterator<Item = EdgeView<'a, S>> {
+        let first = self.view.edge_at(self.view.vertex_edge(self.id) ^ 1);
+
+        successors(Some(first), move |e| {
+            Some(e.twin().next()).filter(|n| n.id != first.id)
+        })
+    }
+
+    #[inline]
+    pub fn incoming_edges(&self) -> impl Iterator<Item = EdgeView<'a, S>> {
+        self.outgoing_edges().map(|edge| edge.twin())
+    }
+
+    #[inline]
+    pub fn neighbors(&self) -> impl Iterator<Item = VertexView<'a, S>> {
+        self.outgoing_edges().map(|edge| edge.destination())
+    }
+
+    #[inline]
+    pub fn faces(&self) -> impl Iterator<Item = FaceView<'a, S>> {
+        self.outgoing_edges().map(|edge| edge.left_face())
+    }
+}
+
+impl<S: Float> Records for GraphView<'_, S> {
+    #[inline]
+    fn half_edge_count(&self) -> usize {
+        self.header.edges
+    }
+
+    #[inline]
+    fn face_count(&self) -> usize {
+        self.header.faces
+    }
+
+    #[inline]
+    fn vertex_count(&self) -> usize {
+        self.header.vertices
+    }
+
+    #[inline]
+    fn point_count(&self) -> usize {
+        self.header.points
+    }
+
+    #[inline]
+    fn half_edge(&self, id: usize) -> GraphEdge {
+        GraphEdge::new(
+            self.index(1, id, 0, 3),
+            self.index(1, id, 1, 3),
+            self.index(1, id, 2, 3),
+        )
+    }
+
+    #[inline]
+    fn face_edge(&self, id: usize) -> usize {
+        self.index(2, id, 0, 1)
+    }
+
+    #[inline]
+    fn vertex_edge(&self, id: usize) -> usize {
+        self.index(3, id, 0, 2)
+    }
+
+    #[inline]
+    fn vertex_point(&self, id: usize) -> Option<usize> {
+        let width = self.header.index;
+        let offset = self.offsets[3] + (id * 2 + 1) * width;
+        let point = read(self.bytes, offset, width);
+        if point == u64::MAX >> (64 - 8 * width) {
+            None
+        } else {
+            Some(point as usize)
+        }
+    }
+
+    #[inline]
+    fn point(&self, id: usize) -> (f64, f64) {
+        let offset = self.offsets[0] + id * 2 * self.header.scalar;
+        (
+            self.scalar(offset).to_f64(),
+            self.scalar(offset + self.header.scalar).to_f64(),
+        )
+    }
+}
+
+impl<S: Float> Graph<(), S> {
+    /// Writes the graph in the binary format, with 32-bit indices whenever they fit.
+    pub fn write_binary<W: Write>(&self, writer: W) -> io::Result<()> {
+        let scalar = std::mem::size_of::<S>();
+        let counts = [
+            self.points.len(),
+            self.edges.len(),
+            self.faces.len(),
+            self.vertices.len(),
+        ];
+        let index = if counts.iter().all(|&count| count < u32::MAX as usize) {
+            4
+        } else {
+            8
+        };
+        let infinite = u64::MAX >> (64 - 8 * index);
+
+        let mut writer = Writer {
+            writer: BufWriter::new(writer),
+            checksum: Checksum::new(),
+        };
+        writer.bytes(MAGIC)?;
+        writer.bytes(&VERSION.to_le_bytes())?;
+        writer.bytes(&[scalar as u8, index as u8])?;
+        for &count in &counts {
+            writer.uint(count as u64, 8)?;
+        }
+
+        for point in &self.points {
+            for &value in &[point.x.to_f64(), point.y.to_f64()] {
+                if scalar == 4 {
+                    writer.bytes(&(value as f32).to_le_bytes())?;
+                } else {
+                    writer.bytes(&value.to_le_bytes())?;
+                }
+            }
+        }
+        for edge in &self.edges {
+            writer.uint(edge.vertex() as u64, index)?;
+            writer.uint(edge.next() as u64, index)?;
+            writer.uint(edge.face() as u64, index)?;
+        }
+        for face in &self.faces {
+            writer.uint(face.edge() as u64, index)?;
+        }
+        for vertex in &self.vertices {
+            writer.uint(vertex.edge() as u64, index)?;
+            let point = vertex
+                .position()
+                .left()
+                .map_or(infinite, |point| point as u64);
+            writer.uint(point, index)?;
+        }
+
+        let checksum = writer.checksum.finish();
+        writer.writer.write_all(&checksum.to_le_bytes())?;
+        writer.writer.flush()
+    }
+
+    /// Reads a graph in the binary format, checking it like [`GraphView::new`].
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Ok(GraphView::new(&bytes)?.to_graph())
+    }
+}
+
+impl<S: Float> Delaunay<S> {
+    /// Writes the triangulation in the binary format, like [`Graph::write_binary`].
+    #[inline]
+    pub fn write_binary<W: Write>(&self, writer: W) -> io::Result<()> {
+        Graph::write_binary(self, writer)
+    }
+
+    /// Reads a triangulation in the binary format, checking it like [`GraphView::new`] and
+    /// then as a triangulation: only triangles, around the single infinite vertex 0. A file
+    /// written from any other graph fails with `InvalidData`.
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let view = GraphView::new(&bytes)?;
+        triangulation(&view).map_err(invalid)?;
+        Ok(Delaunay::from_checked(view.to_graph()))
+    }
+}
+
+#[cfg(feature = "mmap")]
+mod mapped {
+    use super::GraphView;
+    use crate::Float;
+    use memmap2::Mmap;
+    use std::fs::File;
+    use std::io;
+    use std::marker::PhantomData;
+    use std::path::Path;
+
+    /// Binary graph file mapped into memory, checked in full once when opened.
+    pub struct MappedGraph<S: Float = f64> {
+        map: Mmap,
+        scalar: PhantomData<S>,
+    }
+
+    impl<S: Float> MappedGraph<S> {
+        /// Maps the file at `path`. The file must not be modified while it is mapped.
+        pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+            let file = File::open(path)?;
+            // Safety: views only ever read the map, and `open` documents that the file must
+            // stay unchanged while mapped.
+            let map = unsafe { Mmap::map(&file)? };
+            GraphView::<S>::new(&map)?;
+            Ok(MappedGraph {
+                map,
+                scalar: PhantomData,
+            })
+        }
+
+        #[inline]
+        pub fn view(&self) -> GraphView<'_, S> {
+            GraphView::unchecked(&self.map).unwrap()
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use self::mapped::MappedGraph;
@@ -0,0 +1,147 @@
+use crate::graph::{Graph, GraphEdge, GraphFace, GraphVertex};
+use crate::{FaceId, Float, Point, ValidationError, VertexId};
+use either::{Left, Right};
+use std::collections::HashMap;
+
+impl<S: Float> Graph<(), S> {
+    // Builds a graph from counter-clockwise polygons over `points`. Vertex `i + 1` is
+    // `points[i]` and the open boundary is closed with triangles on the infinite vertex 0,
+    // appended after the given faces. Each hole in the polygons is closed the same way on an
+    // infinite vertex of its own, numbered after the points.
+    pub(crate) fn from_polygons(
+        points: Vec<Point<S>>,
+        polygons: &[Vec<usize>],
+    ) -> Result<Self, ValidationError> {
+        let mut edges: Vec<GraphEdge> = Vec::new();
+        let mut faces = Vec::with_capacity(polygons.len());
+        let mut rings = vec![None; points.len() + 1];
+
+        let mut directed = HashMap::new();
+        let mut assigned = Vec::new();
+
+        let mut half_edge = |edges: &mut Vec<GraphEdge>, u: usize, v: usize| {
+            if let Some(&edge) = directed.get(&(u, v)) {
+                return edge;
+            }
+
+            let edge = edges.len();
+            edges.push(GraphEdge::new(u + 1, 0, 0));
+            edges.push(GraphEdge::new(v + 1, 0, 0));
+            directed.insert((u, v), edge);
+            directed.insert((v, u), edge ^ 1);
+            edge
+        };
+
+        for (face, polygon) in polygons.iter().enumerate() {
+            let cycle = (0..polygon.len())
+                .map(|i| half_edge(&mut edges, polygon[i], polygon[(i + 1) % polygon.len()]))
+                .collect::<Vec<_>>();
+
+            assigned.resize(edges.len(), false);
+            for (i, &edge) in cycle.iter().enumerate() {
+                if assigned[edge] {
+                    return Err(ValidationError::BrokenFace(FaceId::new(face)));
+                }
+                assigned[edge] = true;
+
+                let next = cycle[(i + 1) % cycle.len()];
+                edges[edge].set_next(next ^ 1);
+                edges[edge ^ 1].set_face(face);
+                rings[edges[next].vertex()] = Some(edge);
+            }
+
+            faces.push(GraphFace::new(cycle[0]));
+        }
+
+        let boundary = (0..edges.len())
+            .filter(|&edge| !assigned[edge])
+            .collect::<Vec<_>>();
+        let mut leaving = HashMap::with_capacity(boundary.len());
+        for &edge in &boundary {
+            let vertex = edges[edge].vertex();
+            if leaving.insert(vertex, edge).is_some() {
+                return Err(ValidationError::BrokenVertex(VertexId::new(vertex)));
+            }
+        }
+
+        // The boundary runs clockwise around the outside and counter-clockwise around holes.
+        let mut loops = Vec::new();
+        let mut visited = vec![false; edges.len()];
+        for &first in &boundary {
+            let mut edge = first;
+            let mut cycle = Vec::new();
+            while !visited[edge] {
+                visited[edge] = true;
+                cycle.push(edge);
+                let destination = edges[edge ^ 1].vertex();
+                edge = match leaving.get(&destination) {
+                    Some(&next) => next,
+                    None => return Err(ValidationError::BrokenVertex(VertexId::new(destination))),
+                };
+            }
+            if edge != first {
+                let vertex = edges[edge].vertex();
+                return Err(ValidationError::BrokenVertex(VertexId::new(vertex)));
+            }
+            if !cycle.is_empty() {
+                let corners = cycle
+                    .iter()
+                    .map(|&edge| points[edges[edge].vertex() - 1])
+                    .collect::<Vec<_>>();
+                loops.push((crate::cell::signed_area(&corners), cycle));
+            }
+        }
+        loops.sort_by(|(l, _), (r, _)| l.partial_cmp(r).unwrap_or(std::cmp::Ordering::Equal));
+
+        for (i, (_, cycle)) in loops.into_iter().enumerate() {
+            let hub = if i == 0 {
+                0
+            } else {
+                rings.push(None);
+                rings.len() - 1
+            };
+
+            let mut spokes = HashMap::with_capacity(cycle.len());
+            for &edge in &cycle {
+                let vertex = edges[edge].vertex();
+                let spoke = edges.len();
+                edges.push(GraphEdge::new(hub, 0, 0));
+                edges.push(GraphEdge::new(vertex, 0, 0));
+                spokes.insert(vertex, spoke);
+            }
+
+            for &edge in &cycle {
+                let face = faces.len();
+                let origin = edges[edge].vertex();
+                let destination = edges[edge ^ 1].vertex();
+                let (incoming, outgoing) = (spokes[&destination] ^ 1, spokes[&origin]);
+
+                let cycle = [edge, incoming, outgoing];
+                for (i, &e) in cycle.iter().enumerate() {
+                    edges[e].set_next(cycle[(i + 1) % 3] ^ 1);
+                    edges[e ^ 1].set_face(face);
+                }
+                rings[destination] = Some(edge);
+                rings[hub] = Some(incoming);
+
+                faces.push(GraphFace::new(edge));
+            }
+        }
+
+        let vertices = rings
+            .into_iter()
+            .enumerate()
+            .map(|(i, ring)| {
+                let position = if i == 0 || i > points.len() {
+                    Right(())
+                } else {
+                    Left(i - 1)
+                };
+                ring.map(|edge| GraphVertex::new(edge, position))
+                    .ok_or(ValidationError::BrokenVertex(VertexId::new(i)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Graph::new(points, edges, faces, vertices))
+    }
+}
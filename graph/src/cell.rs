@@ -0,0 +1,154 @@
+use crate::{FaceId, Float, Point, Ray, VertexId, Voronoi};
+use either::{Left, Right};
+
+#[derive(Clone, Debug)]
+pub enum Cell<S: Float = f64> {
+    /// Closed counter-clockwise polygon.
+    Bounded(Vec<Point<S>>),
+    /// Counter-clockwise chain closed at infinity: `rays.0` leaves the first point and
+    /// `rays.1` the last one.
+    Unbounded {
+        points: Vec<Point<S>>,
+        rays: (Ray<S>, Ray<S>),
+    },
+}
+
+impl<S: Float> Cell<S> {
+    #[inline]
+    pub fn points(&self) -> &[Point<S>] {
+        match self {
+            Cell::Bounded(points) | Cell::Unbounded { points, .. } => points,
+        }
+    }
+
+    #[inline]
+    pub fn is_bounded(&self) -> bool {
+        matches!(self, Cell::Bounded(_))
+    }
+
+    #[inline]
+    pub fn area(&self) -> Option<S> {
+        match self {
+            Cell::Bounded(points) => Some(S::from_f64(signed_area(points))),
+            Cell::Unbounded { .. } => None,
+        }
+    }
+
+    /// Centroid of the enclosed area, falling back to the vertex mean for degenerate cells.
+    #[inline]
+    pub fn centroid(&self) -> Option<Point<S>> {
+        match self {
+            Cell::Bounded(points) => Some(centroid(points)),
+            Cell::Unbounded { .. } => None,
+        }
+    }
+
+    #[inline]
+    pub fn perimeter(&self) -> Option<S> {
+        match self {
+            Cell::Bounded(points) => Some(S::from_f64(
+                (0..points.len())
+                    .map(|i| {
+                        let p0 = points[i];
+                        let p1 = points[(i + 1) % points.len()];
+                        (p1.x.to_f64() - p0.x.to_f64()).hypot(p1.y.to_f64() - p0.y.to_f64())
+                    })
+                    .sum(),
+            )),
+            Cell::Unbounded { .. } => None,
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn signed_area<S: Float>(points: &[Point<S>]) -> f64 {
+    (0..points.len()).fold(0.0, |area, i| {
+        let p0 = points[i];
+        let p1 = points[(i + 1) % points.len()];
+        area + p0.x.to_f64() * p1.y.to_f64() - p1.x.to_f64() * p0.y.to_f64()
+    }) / 2.0
+}
+
+pub(crate) fn centroid<S: Float>(points: &[Point<S>]) -> Point<S> {
+    let (mut x, mut y, mut area) = (0.0, 0.0, 0.0);
+    for i in 0..points.len() {
+        let (x0, y0) = (points[i].x.to_f64(), points[i].y.to_f64());
+        let p1 = points[(i + 1) % points.len()];
+        let (x1, y1) = (p1.x.to_f64(), p1.y.to_f64());
+
+        let cross = x0 * y1 - x1 * y0;
+        x += (x0 + x1) * cross;
+        y += (y0 + y1) * cross;
+        area += cross;
+    }
+
+    if area.abs() > f64::EPSILON {
+        Point::new(S::from_f64(x / (3.0 * area)), S::from_f64(y / (3.0 * area)))
+    } else {
+        let n = points.len() as f64;
+        let (x, y) = points
+            .iter()
+            .fold((0.0, 0.0), |(x, y), p| (x + p.x.to_f64(), y + p.y.to_f64()));
+        Point::new(S::from_f64(x / n), S::from_f64(y / n))
+    }
+}
+
+impl<S: Float> Voronoi<S> {
+    /// Cell of the Delaunay vertex `site`, or `None` for the infinite vertex.
+    pub fn cell(&self, site: VertexId) -> Option<Cell<S>> {
+        let positions = self
+            .face(FaceId::new(site.index()))
+            .vertices()
+            .map(|vertex| vertex.position())
+            .collect::<Vec<_>>();
+
+        let len = positions.len();
+        let start =
+            (0..len).find(|&i| positions[i].is_left() && positions[(i + len - 1) % len].is_right());
+        let start = match start {
+            Some(start) => start,
+            None if positions.iter().all(|p| p.is_left()) => {
+                return Some(Cell::Bounded(
+                    positions.into_iter().filter_map(|p| p.left()).collect(),
+                ))
+            }
+            None => return None,
+        };
+
+        let first = positions[(start + len - 1) % len].right()?;
+        let mut points = Vec::with_capacity(len);
+        let mut last = None;
+        for i in 0..len {
+            match positions[(start + i) % len] {
+                Left(point) => points.push(point),
+                Right(ray) => {
+                    last = Some(ray);
+                    break;
+                }
+            }
+        }
+
+        // A flat hull triangle gives a ray whose origin is not on this cell.
+        let rays = (
+            first.with_origin(points[0]),
+            last?.with_origin(points[points.len() - 1]),
+        );
+        Some(Cell::Unbounded { points, rays })
+    }
+
+    /// Sites whose cells share an edge with the cell of `site`.
+    #[inline]
+    pub fn neighbor_sites(&self, site: VertexId) -> impl Iterator<Item = VertexId> + '_ {
+        self.face(FaceId::new(site.index()))
+            .neighbors()
+            .map(|face| VertexId::new(face.id().index()))
+            .filter(|&neighbor| neighbor != VertexId::new(0))
+    }
+
+    #[inline]
+    pub fn cells(&self) -> impl Iterator<Item = (VertexId, Cell<S>)> + '_ {
+        (0..self.face_count())
+            .map(VertexId::new)
+            .filter_map(move |site| self.cell(site).map(|cell| (site, cell)))
+    }
+}
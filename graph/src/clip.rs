@@ -0,0 +1,571 @@
+use crate::graph::Graph;
+use crate::{Float, Point, Ray, ValidationError, VertexId, Voronoi};
+use either::{Left, Right};
+
+#[derive(Clone, Debug)]
+pub enum Bounds<S: Float = f64> {
+    /// Axis-aligned rectangle between two opposite corners.
+    Rect(Point<S>, Point<S>),
+    /// Simple polygon in either orientation.
+    Polygon(Vec<Point<S>>),
+}
+
+impl<S: Float> Bounds<S> {
+    #[inline]
+    pub fn unit() -> Self {
+        Bounds::Rect(
+            Point::new(S::zero(), S::zero()),
+            Point::new(S::one(), S::one()),
+        )
+    }
+
+    /// Even-odd test, so points on the boundary may land on either side.
+    pub fn contains(&self, point: &Point<S>) -> bool {
+        let (x, y) = (point.x.to_f64(), point.y.to_f64());
+        let corners = self.corners();
+
+        (0..corners.len()).fold(false, |inside, i| {
+            let (x0, y0) = corners[i];
+            let (x1, y1) = corners[(i + 1) % corners.len()];
+            if (y0 > y) != (y1 > y) && x < x0 + (y - y0) / (y1 - y0) * (x1 - x0) {
+                !inside
+            } else {
+                inside
+            }
+        })
+    }
+
+    pub(crate) fn extent(&self) -> ((f64, f64), (f64, f64)) {
+        self.corners().iter().fold(
+            (
+                (f64::INFINITY, f64::INFINITY),
+                (f64::NEG_INFINITY, f64::NEG_INFINITY),
+            ),
+            |((x0, y0), (x1, y1)), &(x, y)| ((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y))),
+        )
+    }
+
+    fn corners(&self) -> Vec<(f64, f64)> {
+        match self {
+            Bounds::Rect(p0, p1) => {
+                let (x0, x1) = (
+                    p0.x.to_f64().min(p1.x.to_f64()),
+                    p0.x.to_f64().max(p1.x.to_f64()),
+                );
+                let (y0, y1) = (
+                    p0.y.to_f64().min(p1.y.to_f64()),
+                    p0.y.to_f64().max(p1.y.to_f64()),
+                );
+                vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1)]
+            }
+            Bounds::Polygon(points) => {
+                let mut corners = points
+                    .iter()
+                    .map(|p| (p.x.to_f64(), p.y.to_f64()))
+                    .collect::<Vec<_>>();
+                let area = (0..corners.len()).fold(0.0, |area, i| {
+                    let (x0, y0) = corners[i];
+                    let (x1, y1) = corners[(i + 1) % corners.len()];
+                    area + x0 * y1 - x1 * y0
+                });
+                if area < 0.0 {
+                    corners.reverse();
+                }
+                corners
+            }
+        }
+    }
+}
+
+type Segment = ((f64, f64), (f64, f64));
+
+// Where a Voronoi edge passes through the bounds: `t` grows along the edge from the origin of
+// its even half-edge and `at` runs along the boundary, side `i` covering `i..i + 1`.
+#[derive(Clone, Copy, Debug)]
+struct Crossing {
+    node: usize,
+    t: f64,
+    at: f64,
+}
+
+// A step around a cell: a Voronoi vertex inside the bounds, or the cell boundary leaving or
+// entering them.
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    Vertex(usize),
+    Exit(Crossing),
+    Enter(Crossing),
+}
+
+// Side of `point` against the line through a segment, positive on its left. It is measured
+// from the nearer end, as Voronoi vertices of flat triangles may lie very far out.
+#[inline]
+fn side(((x0, y0), (x1, y1)): Segment, (x, y): (f64, f64)) -> f64 {
+    let (bx, by) = if (x - x0).abs() + (y - y0).abs() <= (x - x1).abs() + (y - y1).abs() {
+        (x0, y0)
+    } else {
+        (x1, y1)
+    };
+    (x1 - x0) * (y - by) - (y1 - y0) * (x - bx)
+}
+
+#[inline]
+fn root(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
+
+// Drops repeated nodes and the spikes that contraction folds out of a polygon.
+fn simplify(polygon: &mut Vec<usize>) {
+    loop {
+        let len = polygon.len();
+        polygon.dedup();
+        while polygon.len() > 1 && polygon[0] == polygon[polygon.len() - 1] {
+            polygon.pop();
+        }
+        if polygon.len() > 2 {
+            let spike =
+                (0..polygon.len()).find(|&i| polygon[i] == polygon[(i + 2) % polygon.len()]);
+            if let Some(i) = spike {
+                polygon.remove((i + 1) % polygon.len());
+            }
+        }
+        if polygon.len() == len || polygon.len() < 3 {
+            break;
+        }
+    }
+}
+
+// Clipping works on the Voronoi edges rather than on each cell separately: every edge is cut
+// against the boundary once, so the two cells on either side of it see the same crossings,
+// and the cells are then walked to collect their pieces.
+struct Clipper<'a, S: Float> {
+    voronoi: &'a Voronoi<S>,
+    corners: Vec<(f64, f64)>,
+    segments: Vec<Option<Segment>>,
+    crossings: Vec<Vec<Crossing>>,
+    inside: Vec<Option<usize>>,
+    nodes: Vec<(f64, f64)>,
+}
+
+impl<'a, S: Float> Clipper<'a, S> {
+    fn new(voronoi: &'a Voronoi<S>, bounds: &Bounds<S>) -> Self {
+        let corners = bounds.corners();
+        let ((x0, y0), (x1, y1)) = bounds.extent();
+        let size = (x1 - x0).max(y1 - y0).max(f64::MIN_POSITIVE);
+        let tolerance = 1e-9 * size;
+
+        let position = |vertex: usize| {
+            voronoi
+                .position_of(vertex)
+                .map(|p| (p.x.to_f64(), p.y.to_f64()))
+        };
+        // Rays run from the finite end of their edge and are cut off beyond every corner.
+        let far = |(x, y): (f64, f64), ray: Ray<S>| {
+            let length = corners
+                .iter()
+                .map(|&(cx, cy)| (cx - x).hypot(cy - y))
+                .fold(size, f64::max);
+            let direction = ray.direction();
+            (
+                x + 2.0 * length * direction.x.to_f64(),
+                y + 2.0 * length * direction.y.to_f64(),
+            )
+        };
+
+        let segments = (0..voronoi.edges.len())
+            .step_by(2)
+            .map(|edge| {
+                let (u, v) = (voronoi.origin_of(edge), voronoi.origin_of(edge ^ 1));
+                match (
+                    voronoi.vertices[u].position(),
+                    voronoi.vertices[v].position(),
+                ) {
+                    (Left(_), Left(_)) => Some((position(u)?, position(v)?)),
+                    (Left(_), Right(ray)) => position(u).map(|p| (p, far(p, ray))),
+                    (Right(ray), Left(_)) => position(v).map(|p| (far(p, ray), p)),
+                    (Right(_), Right(_)) => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Ties are broken as if the bounds were shrunk by an infinitesimal margin: points on a
+        // side count as outside, and a segment through a corner passes it on the side the
+        // corner moves to, along its miter and then towards the next corner.
+        let len = corners.len();
+        let normal = |i: usize| {
+            let ((x0, y0), (x1, y1)) = (corners[i], corners[(i + 1) % len]);
+            let length = (x1 - x0).hypot(y1 - y0).max(f64::MIN_POSITIVE);
+            ((y0 - y1) / length, (x1 - x0) / length)
+        };
+        let miters = (0..len)
+            .map(|i| {
+                let ((ax, ay), (bx, by)) = (normal((i + len - 1) % len), normal(i));
+                let scale = (1.0 + ax * bx + ay * by).max(f64::EPSILON);
+                ((ax + bx) / scale, (ay + by) / scale)
+            })
+            .collect::<Vec<_>>();
+        let corner_side = |segment: Segment, i: usize| {
+            let ((x0, y0), (x1, y1)) = segment;
+            let cross = |(x, y): (f64, f64)| (x1 - x0) * y - (y1 - y0) * x;
+            let ((cx, cy), (nx, ny)) = (corners[i], corners[(i + 1) % len]);
+            [
+                side(segment, corners[i]),
+                cross(miters[i]),
+                cross((nx - cx, ny - cy)),
+            ]
+            .iter()
+            .copied()
+            .find(|&side| side != 0.0)
+            .unwrap_or(0.0)
+        };
+
+        let mut nodes = corners.clone();
+        let crossings = segments
+            .iter()
+            .map(|segment| {
+                let (p0, p1) = match *segment {
+                    Some(segment) => segment,
+                    None => return Vec::new(),
+                };
+
+                let mut crossings = Vec::new();
+                for i in 0..len {
+                    let j = (i + 1) % len;
+                    let (q0, q1) = (corners[i], corners[j]);
+                    let (o0, o1) = (side((q0, q1), p0), side((q0, q1), p1));
+                    let (c0, c1) = (corner_side((p0, p1), i), corner_side((p0, p1), j));
+                    if (o0 > 0.0) == (o1 > 0.0) || (c0 > 0.0) == (c1 > 0.0) {
+                        continue;
+                    }
+
+                    let (r0, r1) = (side((p0, p1), q0), side((p0, p1), q1));
+                    let s = (r0 / (r0 - r1)).clamp(0.0, 1.0);
+                    let point = (q0.0 + s * (q1.0 - q0.0), q0.1 + s * (q1.1 - q0.1));
+                    let t = point.0 * (p1.0 - p0.0) + point.1 * (p1.1 - p0.1);
+                    let near = |(x, y): (f64, f64)| (x - point.0).hypot(y - point.1) <= tolerance;
+                    let (node, at) = if near(q0) {
+                        (i, i as f64)
+                    } else if near(q1) {
+                        (j, j as f64)
+                    } else {
+                        nodes.push(point);
+                        (nodes.len() - 1, i as f64 + s)
+                    };
+                    crossings.push(Crossing { node, t, at });
+                }
+                crossings.sort_unstable_by(|a, b| a.t.total_cmp(&b.t));
+                crossings
+            })
+            .collect::<Vec<_>>();
+
+        // Rays end outside, and every edge from there flips sides at each crossing, so the
+        // vertices agree with the crossings of the edges that reach them.
+        let odd = |edge: usize| crossings[edge >> 1].len() % 2 == 1;
+        let mut sides = vec![None; voronoi.vertices.len()];
+        let mut stack = Vec::new();
+        for edge in 0..voronoi.edges.len() {
+            let (u, v) = (voronoi.origin_of(edge), voronoi.origin_of(edge ^ 1));
+            let ray = voronoi.vertices[v].position().is_right();
+            if ray && segments[edge >> 1].is_some() && sides[u].is_none() {
+                sides[u] = Some(odd(edge));
+                stack.push(u);
+            }
+        }
+        while let Some(u) = stack.pop() {
+            for edge in voronoi.outgoing_of(u) {
+                let v = voronoi.origin_of(edge ^ 1);
+                if sides[v].is_none() && segments[edge >> 1].is_some() {
+                    sides[v] = Some(sides[u] != Some(odd(edge)));
+                    stack.push(v);
+                }
+            }
+        }
+
+        let inside = (0..voronoi.vertices.len())
+            .map(|vertex| {
+                let point = voronoi.position_of(vertex)?;
+                if sides[vertex].unwrap_or_else(|| bounds.contains(point)) {
+                    nodes.push((point.x.to_f64(), point.y.to_f64()));
+                    Some(nodes.len() - 1)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let mut clipper = Clipper {
+            voronoi,
+            corners,
+            segments,
+            crossings,
+            inside,
+            nodes,
+        };
+        clipper.contract(tolerance);
+        clipper
+    }
+
+    // Contracts the pieces of the clipped diagram shorter than `tolerance`: Voronoi edges
+    // between cocircular sites, and crossings next to a corner, a Voronoi vertex or each other.
+    // Merging only along edges keeps the pieces stitched together, which merging every pair of
+    // close points would not. Afterwards every node refers to the first node it was merged with.
+    fn contract(&mut self, tolerance: f64) {
+        let nodes = &self.nodes;
+        let mut parents = (0..nodes.len()).collect::<Vec<_>>();
+        let mut join = |a: usize, b: usize| {
+            let ((x0, y0), (x1, y1)) = (nodes[a], nodes[b]);
+            if (x1 - x0).hypot(y1 - y0) <= tolerance {
+                let (a, b) = (root(&mut parents, a), root(&mut parents, b));
+                parents[a.max(b)] = a.min(b);
+            }
+        };
+
+        // Each edge runs inside between its first and second node, third and fourth, and so on.
+        for (edge, crossings) in self.crossings.iter().enumerate() {
+            let u = self.inside[self.voronoi.origin_of(edge << 1)];
+            let v = self.inside[self.voronoi.origin_of((edge << 1) | 1)];
+            let along = u
+                .into_iter()
+                .chain(crossings.iter().map(|crossing| crossing.node))
+                .chain(v)
+                .collect::<Vec<_>>();
+            for piece in along.chunks_exact(2) {
+                join(piece[0], piece[1]);
+            }
+        }
+
+        let mut boundary = (0..self.corners.len())
+            .map(|corner| (corner as f64, corner))
+            .chain(
+                self.crossings
+                    .iter()
+                    .flatten()
+                    .map(|crossing| (crossing.at, crossing.node)),
+            )
+            .collect::<Vec<_>>();
+        boundary.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        for i in 0..boundary.len() {
+            join(boundary[i].1, boundary[(i + 1) % boundary.len()].1);
+        }
+
+        let roots = (0..parents.len())
+            .map(|i| root(&mut parents, i))
+            .collect::<Vec<_>>();
+        let remap = |node: &mut usize| *node = roots[*node];
+        self.crossings
+            .iter_mut()
+            .flatten()
+            .for_each(|crossing| remap(&mut crossing.node));
+        self.inside.iter_mut().flatten().for_each(remap);
+    }
+
+    // Node indices of the pieces of the cell `face` inside the bounds, walking
+    // counter-clockwise and following the boundary wherever the cell leaves it. Concave bounds
+    // may cut a cell into several pieces.
+    fn pieces(&self, face: usize) -> Vec<Vec<usize>> {
+        let voronoi = self.voronoi;
+        let mut steps = Vec::new();
+        for edge in voronoi.cycle_of(voronoi.faces[face].edge()) {
+            if self.segments[edge >> 1].is_none() {
+                continue;
+            }
+
+            let origin = self.inside[voronoi.origin_of(edge)];
+            if let Some(node) = origin {
+                steps.push(Step::Vertex(node));
+            }
+
+            let crossings = &self.crossings[edge >> 1];
+            let ordered: Box<dyn Iterator<Item = &Crossing>> = if edge & 1 == 0 {
+                Box::new(crossings.iter())
+            } else {
+                Box::new(crossings.iter().rev())
+            };
+            let mut inside = origin.is_some();
+            for &crossing in ordered {
+                steps.push(if inside {
+                    Step::Exit(crossing)
+                } else {
+                    Step::Enter(crossing)
+                });
+                inside = !inside;
+            }
+        }
+
+        let first = match steps.iter().position(|step| matches!(step, Step::Enter(_))) {
+            Some(first) => first,
+            None => {
+                return vec![steps
+                    .into_iter()
+                    .filter_map(|step| match step {
+                        Step::Vertex(node) => Some(node),
+                        _ => None,
+                    })
+                    .collect()]
+            }
+        };
+
+        // Runs of the cell boundary inside the bounds, each from where it enters them to
+        // where it leaves.
+        let mut runs: Vec<(Crossing, Vec<usize>, Option<Crossing>)> = Vec::new();
+        for &step in steps[first..].iter().chain(&steps[..first]) {
+            match step {
+                Step::Enter(crossing) => runs.push((crossing, vec![crossing.node], None)),
+                Step::Vertex(node) => runs.last_mut().unwrap().1.push(node),
+                Step::Exit(crossing) => {
+                    let run = runs.last_mut().unwrap();
+                    run.1.push(crossing.node);
+                    run.2 = Some(crossing);
+                }
+            }
+        }
+
+        // Leaving the bounds, a piece follows their boundary to the first place the cell enters
+        // them again, which is the next run only if the bounds are convex.
+        let len = self.corners.len() as f64;
+        let next = runs
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, _, exit))| {
+                let exit = match exit {
+                    Some(exit) => exit,
+                    None => return (i + 1) % runs.len(),
+                };
+                let distance = |enter: &Crossing| {
+                    if enter.node == exit.node {
+                        0.0
+                    } else {
+                        (enter.at - exit.at).rem_euclid(len)
+                    }
+                };
+                (0..runs.len())
+                    .min_by(|&a, &b| distance(&runs[a].0).total_cmp(&distance(&runs[b].0)))
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let mut pieces = Vec::new();
+        let mut visited = vec![false; runs.len()];
+        for start in 0..runs.len() {
+            let mut polygon = Vec::with_capacity(steps.len() + self.corners.len());
+            let mut run = start;
+            while !visited[run] {
+                visited[run] = true;
+                let (_, nodes, exit) = &runs[run];
+                polygon.extend_from_slice(nodes);
+                if let Some(exit) = exit {
+                    self.follow(&mut polygon, *exit, runs[next[run]].0);
+                }
+                run = next[run];
+            }
+            if !polygon.is_empty() {
+                pieces.push(polygon);
+            }
+        }
+        pieces
+    }
+
+    // Pushes the corners met going counter-clockwise along the boundary from `exit` to
+    // `enter`, none if both snapped to the same node.
+    fn follow(&self, polygon: &mut Vec<usize>, exit: Crossing, enter: Crossing) {
+        if exit.node == enter.node {
+            return;
+        }
+
+        let len = self.corners.len();
+        let span = (enter.at - exit.at).rem_euclid(len as f64);
+        let start = exit.at.floor();
+        for k in 1..=len {
+            if start + k as f64 - exit.at < span {
+                polygon.push((start as usize + k) % len);
+            }
+        }
+    }
+
+    // The cell holding the whole bounds when no edge reaches them.
+    fn enclosing(&self) -> Option<usize> {
+        let voronoi = self.voronoi;
+        (0..voronoi.faces.len()).find(|&face| {
+            let cycle = voronoi.cycle_of(voronoi.faces[face].edge());
+            cycle.iter().any(|&edge| self.segments[edge >> 1].is_some())
+                && cycle.iter().all(|&edge| match self.segments[edge >> 1] {
+                    Some((p0, p1)) if edge & 1 == 0 => side((p0, p1), self.corners[0]) >= 0.0,
+                    Some((p0, p1)) => side((p1, p0), self.corners[0]) >= 0.0,
+                    None => true,
+                })
+        })
+    }
+}
+
+impl<S: Float> Voronoi<S> {
+    /// Cuts every cell to `bounds` and stitches the pieces into a closed graph: one finite face
+    /// per piece of a surviving cell, in site order, followed by triangles fanning out to the
+    /// infinite vertex 0. Face attributes of the cells are carried over and each face gets a
+    /// `"site"` attribute with the Delaunay vertex of its cell (0 for the outer faces).
+    ///
+    /// Pieces shorter than a billionth of the bounds' size are contracted, so sites that are
+    /// cocircular or whose bisector runs through a corner give shared vertices rather than
+    /// slivers. Concave polygon bounds may cut a cell into several pieces, which then share
+    /// their site. Fails when every site lies on one line.
+    pub fn clipped(&self, bounds: &Bounds<S>) -> Result<Graph<(), S>, ValidationError> {
+        let clipper = Clipper::new(self, bounds);
+
+        let mut pieces = (0..self.faces.len())
+            .flat_map(|face| {
+                clipper
+                    .pieces(face)
+                    .into_iter()
+                    .map(move |polygon| (face, polygon))
+            })
+            .filter_map(|(face, mut polygon)| {
+                simplify(&mut polygon);
+                if polygon.len() >= 3 {
+                    Some((face, polygon))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        if pieces.is_empty() {
+            if let Some(face) = clipper.enclosing() {
+                pieces.push((face, (0..clipper.corners.len()).collect()));
+            }
+        }
+
+        let mut indices = vec![usize::MAX; clipper.nodes.len()];
+        let mut points = Vec::new();
+        let mut polygons = Vec::with_capacity(pieces.len());
+        let mut sites = Vec::with_capacity(pieces.len());
+        for (face, polygon) in pieces {
+            let polygon: Vec<usize> = polygon
+                .into_iter()
+                .map(|node| {
+                    if indices[node] == usize::MAX {
+                        let (x, y) = clipper.nodes[node];
+                        indices[node] = points.len();
+                        points.push(Point::new(S::from_f64(x), S::from_f64(y)));
+                    }
+                    indices[node]
+                })
+                .collect();
+            polygons.push(polygon);
+            sites.push(face);
+        }
+
+        let mut graph = Graph::from_polygons(points, &polygons)?;
+        graph.validate()?;
+
+        let count = graph.face_count();
+        graph.face_attributes = self.face_attributes.select(&sites, count);
+
+        let mut site = sites.into_iter().map(VertexId::new).collect::<Vec<_>>();
+        site.resize(count, VertexId::default());
+        graph.face_attributes.insert("site", site).unwrap();
+
+        Ok(graph)
+    }
+}
@@ -0,0 +1,267 @@
+//! Point cloud readers for delimited text: CSV and whitespace-separated XYZ.
+
+use crate::{Attribute, Delaunay, Float, Point};
+use std::fmt::Display;
+use std::io::{self, BufRead};
+
+/// Column of a delimited file, by zero-based position or by header name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for Column {
+    #[inline]
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    #[inline]
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_owned())
+    }
+}
+
+/// Points read from a file, with every other column as one value per point.
+#[derive(Clone, Debug)]
+pub struct PointCloud<S: Float = f64> {
+    pub points: Vec<Point<S>>,
+    pub columns: Vec<(String, Vec<S>)>,
+}
+
+impl<S: Float + Attribute> PointCloud<S> {
+    /// Triangulates the points, carrying the extra columns as site attributes. Of duplicate
+    /// points, only the values of the one kept as a vertex remain. Fails if the points do
+    /// not triangulate or a column does not hold one value per point.
+    #[allow(clippy::result_unit_err)]
+    pub fn delaunay(self) -> Result<Delaunay<S>, ()> {
+        let len = self.points.len();
+        let mut delaunay = Delaunay::from(self.points)?;
+        let points = delaunay
+            .vertices
+            .iter()
+            .filter_map(|vertex| vertex.position().left())
+            .collect::<Vec<_>>();
+        for (name, values) in self.columns {
+            if values.len() != len {
+                return Err(());
+            }
+            let values = points.iter().map(|&point| values[point]).collect();
+            delaunay
+                .insert_site_attribute(&name, values)
+                .map_err(|_| ())?;
+        }
+        Ok(delaunay)
+    }
+}
+
+pub struct PointReader {
+    delimiter: Option<char>,
+    header: bool,
+    comment: Option<char>,
+    names: &'static [&'static str],
+    x: Column,
+    y: Column,
+}
+
+impl PointReader {
+    /// Comma-separated values with a header line, `x` and `y` in the first two columns.
+    #[inline]
+    pub fn csv() -> Self {
+        PointReader {
+            delimiter: Some(','),
+            header: true,
+            comment: None,
+            names: &[],
+            x: Column::Index(0),
+            y: Column::Index(1),
+        }
+    }
+
+    /// Whitespace-separated `x y z …` lines without header, `#` starting a comment.
+    #[inline]
+    pub fn xyz() -> Self {
+        PointReader {
+            delimiter: None,
+            header: false,
+            comment: Some('#'),
+            names: &["x", "y", "z"],
+            x: Column::Index(0),
+            y: Column::Index(1),
+        }
+    }
+
+    /// Splits on `delimiter`, or on runs of whitespace for `None`. With a delimiter, fields
+    /// may be quoted to hold it, a doubled quote standing for one inside them.
+    #[inline]
+    pub fn with_delimiter(mut self, delimiter: Option<char>) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Whether the first line names the columns. Without one, columns are named `column0`,
+    /// `column1`, …, or `x`, `y` and `z` for the first three of an XYZ file.
+    #[inline]
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Ignores everything after `comment` on a line.
+    #[inline]
+    pub fn with_comment(mut self, comment: Option<char>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Columns holding the coordinates. Names need a header.
+    #[inline]
+    pub fn with_columns(mut self, x: impl Into<Column>, y: impl Into<Column>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
+        self
+    }
+
+    fn split(&self, line: &str) -> Result<Vec<String>, String> {
+        let line = match self.comment {
+            Some(comment) => line.split(comment).next().unwrap_or_default(),
+            None => line,
+        };
+        let delimiter = match self.delimiter {
+            Some(delimiter) => delimiter,
+            None => return Ok(line.split_whitespace().map(str::to_owned).collect()),
+        };
+
+        let mut fields = Vec::new();
+        let mut chars = line.chars().peekable();
+        loop {
+            while chars
+                .next_if(|&c| c != delimiter && c.is_whitespace())
+                .is_some()
+            {}
+
+            let mut field = String::new();
+            if chars.next_if_eq(&'"').is_some() {
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                        Some('"') => break,
+                        Some(c) => field.push(c),
+                        None => return Err("unterminated quoted field".to_owned()),
+                    }
+                }
+                while chars
+                    .next_if(|&c| c != delimiter && c.is_whitespace())
+                    .is_some()
+                {}
+                if chars.peek().is_some_and(|&c| c != delimiter) {
+                    return Err("text after a quoted field".to_owned());
+                }
+            } else {
+                while let Some(c) = chars.next_if(|&c| c != delimiter) {
+                    field.push(c);
+                }
+                field.truncate(field.trim_end().len());
+            }
+            fields.push(field);
+
+            if chars.next().is_none() {
+                break Ok(fields);
+            }
+        }
+    }
+
+    fn position(&self, column: &Column, names: &[String]) -> Result<usize, String> {
+        match column {
+            Column::Index(index) => Ok(*index),
+            Column::Name(name) => names
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| format!("no column `{}`", name)),
+        }
+    }
+
+    /// Reads every non-blank line into a point. All columns besides the coordinates must be
+    /// numbers too, and each line must have as many as the first.
+    pub fn read<R: BufRead, S: Float>(&self, reader: R) -> io::Result<PointCloud<S>> {
+        let error = |line: usize, message: &dyn Display| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", line, message),
+            )
+        };
+
+        let mut names: Option<Vec<String>> = None;
+        let mut layout: Option<(usize, usize, Vec<usize>)> = None;
+        let mut cloud = PointCloud {
+            points: Vec::new(),
+            columns: Vec::new(),
+        };
+
+        for (number, line) in reader.lines().enumerate() {
+            let (number, line) = (number + 1, line?);
+            let fields = self.split(&line).map_err(|e| error(number, &e))?;
+            if fields.iter().all(|field| field.is_empty()) {
+                continue;
+            }
+
+            let names = match &names {
+                Some(names) => names,
+                None if self.header => {
+                    names = Some(fields);
+                    continue;
+                }
+                None => names.get_or_insert(
+                    (0..fields.len())
+                        .map(|i| match self.names.get(i) {
+                            Some(name) => (*name).to_owned(),
+                            None => format!("column{}", i),
+                        })
+                        .collect(),
+                ),
+            };
+
+            if layout.is_none() {
+                let x = self.position(&self.x, names);
+                let y = self.position(&self.y, names);
+                let (x, y) = match (x, y) {
+                    (Ok(x), Ok(y)) => (x, y),
+                    (Err(e), _) | (_, Err(e)) => return Err(error(number, &e)),
+                };
+                let extra = (0..names.len())
+                    .filter(|&i| i != x && i != y)
+                    .collect::<Vec<_>>();
+                cloud.columns = extra
+                    .iter()
+                    .map(|&i| (names[i].clone(), Vec::new()))
+                    .collect();
+                layout = Some((x, y, extra));
+            }
+            let (x, y, extra) = layout.as_ref().unwrap();
+
+            if fields.len() != names.len() {
+                let message = format!("{} fields, expected {}", fields.len(), names.len());
+                return Err(error(number, &message));
+            }
+            let value = |i: usize| {
+                let field = fields
+                    .get(i)
+                    .ok_or_else(|| error(number, &format!("no column {}", i)))?;
+                field
+                    .parse::<f64>()
+                    .map(S::from_f64)
+                    .map_err(|_| error(number, &format!("invalid number `{}`", field)))
+            };
+
+            cloud.points.push(Point::new(value(*x)?, value(*y)?));
+            for (&i, (_, values)) in extra.iter().zip(&mut cloud.columns) {
+                values.push(value(i)?);
+            }
+        }
+
+        Ok(cloud)
+    }
+}
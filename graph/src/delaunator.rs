@@ -1,5 +1,7 @@
 use crate::graph::{GraphEdge, GraphFace, GraphVertex};
-use crate::point::Point;
+use crate::point::{Float, Point};
+#[cfg(feature = "validate")]
+use crate::validate::Records;
 use either::{Left, Right};
 use nalgebra::distance_squared;
 
@@ -9,18 +11,18 @@ use std::hint::unreachable_unchecked;
 use rayon::prelude::*;
 
 #[derive(Debug)]
-pub struct Delaunator<'a> {
-    points: &'a [Point],
+pub struct Delaunator<'a, S: Float> {
+    points: &'a [Point<S>],
 
     edges: &'a mut Vec<GraphEdge>,
     faces: &'a mut Vec<GraphFace>,
     vertices: &'a mut Vec<GraphVertex<()>>,
 }
 
-impl<'a> Delaunator<'a> {
+impl<'a, S: Float> Delaunator<'a, S> {
     #[inline]
     pub fn new(
-        points: &'a [Point],
+        points: &'a [Point<S>],
         edges: &'a mut Vec<GraphEdge>,
         faces: &'a mut Vec<GraphFace>,
         vertices: &'a mut Vec<GraphVertex<()>>,
@@ -34,12 +36,12 @@ impl<'a> Delaunator<'a> {
     }
 
     #[inline]
-    fn circumradius(&self, v0: usize, v1: usize, v2: usize) -> f64 {
+    fn circumradius(&self, v0: usize, v1: usize, v2: usize) -> S {
         Point::circumradius(&self.points[v0], &self.points[v1], &self.points[v2])
     }
 
     #[inline]
-    fn circumcenter(&self, v0: usize, v1: usize, v2: usize) -> Point {
+    fn circumcenter(&self, v0: usize, v1: usize, v2: usize) -> Point<S> {
         Point::circumcenter(&self.points[v0], &self.points[v1], &self.points[v2])
     }
 
@@ -65,7 +67,7 @@ impl<'a> Delaunator<'a> {
 
     #[cfg(feature = "use-rayon")]
     #[inline]
-    fn calc_bounding_box_center(&self) -> Option<Point> {
+    fn calc_bounding_box_center(&self) -> Option<Point<S>> {
         self.points
             .par_iter()
             .map(|p| (p.x, p.y, p.x, p.y))
@@ -79,12 +81,17 @@ impl<'a> Delaunator<'a> {
                     (minx, miny, maxx, maxy)
                 },
             )
-            .map(|(minx, miny, maxx, maxy)| Point::new((minx + maxx) / 2., (miny + maxy) / 2.))
+            .map(|(minx, miny, maxx, maxy)| {
+                Point::new(
+                    (minx + maxx) / S::from_f64(2.),
+                    (miny + maxy) / S::from_f64(2.),
+                )
+            })
     }
 
     #[cfg(not(feature = "use-rayon"))]
     #[inline]
-    fn calc_bounding_box_center(&self) -> Option<Point> {
+    fn calc_bounding_box_center(&self) -> Option<Point<S>> {
         let mut iter = self.points.iter();
 
         iter.next()
@@ -111,12 +118,17 @@ impl<'a> Delaunator<'a> {
                     },
                 )
             })
-            .map(|(minx, miny, maxx, maxy)| Point::new((minx + maxx) / 2., (miny + maxy) / 2.))
+            .map(|(minx, miny, maxx, maxy)| {
+                Point::new(
+                    (minx + maxx) / S::from_f64(2.),
+                    (miny + maxy) / S::from_f64(2.),
+                )
+            })
     }
 
     #[cfg(feature = "use-rayon")]
     #[inline]
-    fn find_closest_to_position(&self, center: Point) -> Option<usize> {
+    fn find_closest_to_position(&self, center: Point<S>) -> Option<usize> {
         (0..(self.points.len()))
             .into_par_iter()
             .map(|id| (id, distance_squared(&*center, &*self.points[id])))
@@ -126,7 +138,7 @@ impl<'a> Delaunator<'a> {
 
     #[cfg(not(feature = "use-rayon"))]
     #[inline]
-    fn find_closest_to_position(&self, center: Point) -> Option<usize> {
+    fn find_closest_to_position(&self, center: Point<S>) -> Option<usize> {
         let mut iter =
             (0..(self.points.len())).map(|id| (id, distance_squared(&*center, &*self.points[id])));
 
@@ -186,7 +198,9 @@ impl<'a> Delaunator<'a> {
                 if self.nearly_equals(i, v1) || self.nearly_equals(i, v2) {
                     None
                 } else {
+                    // Collinear corners have no finite circumcircle.
                     Some((i, self.circumradius(v1, v2, i)))
+                        .filter(|(_, radius)| radius.to_f64().is_finite())
                 }
             })
             .reduce_with(|(l, ld), (r, rd)| if ld < rd { (l, ld) } else { (r, rd) })
@@ -200,7 +214,9 @@ impl<'a> Delaunator<'a> {
             if v1 == i || v2 == i || self.nearly_equals(i, v1) || self.nearly_equals(i, v2) {
                 None
             } else {
+                // Collinear corners have no finite circumcircle.
                 Some((i, self.circumradius(v1, v2, i)))
+                    .filter(|(_, radius)| radius.to_f64().is_finite())
             }
         });
 
@@ -257,17 +273,17 @@ impl<'a> Delaunator<'a> {
 
     #[inline]
     fn find_visible_edge(&self, position: usize) -> Option<(usize, bool)> {
-        let initial = self.vertices[0].edge;
+        let initial = self.vertices[0].edge();
         let mut current = initial;
-        let mut current_position = self.vertices[self.edges[current].vertex]
-            .position
+        let mut current_position = self.vertices[self.edges[current].vertex()]
+            .position()
             .left()
             .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
         loop {
-            let next = self.edges[current].next;
-            let next_position = self.vertices[self.edges[next].vertex]
-                .position
+            let next = self.edges[current].next();
+            let next_position = self.vertices[self.edges[next].vertex()]
+                .position()
                 .left()
                 .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
@@ -286,10 +302,10 @@ impl<'a> Delaunator<'a> {
 
     #[inline]
     fn add_triangle(&mut self, vertex: usize, current_edge: usize, next_edge: usize) -> usize {
-        let current_vertex = self.edges[current_edge].vertex;
-        let next_vertex = self.edges[next_edge].vertex;
-        let face = self.edges[next_edge].face;
-        let opposite_edge = self.edges[next_edge ^ 1].next;
+        let current_vertex = self.edges[current_edge].vertex();
+        let next_vertex = self.edges[next_edge].vertex();
+        let face = self.edges[next_edge].face();
+        let opposite_edge = self.edges[next_edge ^ 1].next();
 
         let new_current_face = self.faces.len();
         let new_next_face = new_current_face + 1;
@@ -310,74 +326,74 @@ impl<'a> Delaunator<'a> {
         self.edges
             .push(GraphEdge::new(vertex, opposite_edge, new_next_face));
 
-        self.edges[next_edge ^ 1].next = edge + 5;
-        self.edges[current_edge].next = edge + 3;
-        self.edges[opposite_edge ^ 1].next = edge;
+        self.edges[next_edge ^ 1].set_next(edge + 5);
+        self.edges[current_edge].set_next(edge + 3);
+        self.edges[opposite_edge ^ 1].set_next(edge);
 
-        self.edges[current_edge ^ 1].face = new_current_face;
-        self.edges[next_edge].face = new_next_face;
+        self.edges[current_edge ^ 1].set_face(new_current_face);
+        self.edges[next_edge].set_face(new_next_face);
 
-        self.vertices[vertex].edge = edge + 1;
-        self.vertices[0].edge = edge + 3;
+        self.vertices[vertex].set_edge(edge + 1);
+        self.vertices[0].set_edge(edge + 3);
 
         opposite_edge
     }
 
     fn legalize(&mut self, t0e0: usize) {
-        let t1e1 = self.edges[t0e0].next;
-        let p = self.edges[t1e1].vertex;
+        let t1e1 = self.edges[t0e0].next();
+        let p = self.edges[t1e1].vertex();
 
         if p != 0 {
             let t1e0 = t0e0 ^ 1;
-            let t0e1 = self.edges[t1e0].next;
-            let t0e2 = self.edges[t0e1 ^ 1].next;
+            let t0e1 = self.edges[t1e0].next();
+            let t0e2 = self.edges[t0e1 ^ 1].next();
 
-            let va = self.edges[t0e0].vertex;
-            let vb = self.edges[t1e0].vertex;
-            let v0 = self.edges[t0e1].vertex;
-            let v1 = self.edges[t1e1].vertex;
+            let va = self.edges[t0e0].vertex();
+            let vb = self.edges[t1e0].vertex();
+            let v0 = self.edges[t0e1].vertex();
+            let v1 = self.edges[t1e1].vertex();
 
             let p0 = self.vertices[v0]
-                .position
+                .position()
                 .left()
                 .unwrap_or_else(|| unsafe { unreachable_unchecked() });
             let pa = self.vertices[va]
-                .position
+                .position()
                 .left()
                 .unwrap_or_else(|| unsafe { unreachable_unchecked() });
             let pb = self.vertices[vb]
-                .position
+                .position()
                 .left()
                 .unwrap_or_else(|| unsafe { unreachable_unchecked() });
             let p1 = self.vertices[v1]
-                .position
+                .position()
                 .left()
                 .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
             if self.in_circle(p0, pa, pb, p1) {
-                let t1e2 = self.edges[t1e1 ^ 1].next;
-                let t0 = self.edges[t0e2].face;
-                let t1 = self.edges[t1e2].face;
+                let t1e2 = self.edges[t1e1 ^ 1].next();
+                let t0 = self.edges[t0e2].face();
+                let t1 = self.edges[t1e2].face();
 
-                self.vertices[va].edge = t0e1;
-                self.vertices[vb].edge = t1e1;
+                self.vertices[va].set_edge(t0e1);
+                self.vertices[vb].set_edge(t1e1);
 
-                self.edges[t0e2 ^ 1].next = t1e1;
-                self.edges[t1e2 ^ 1].next = t0e1;
+                self.edges[t0e2 ^ 1].set_next(t1e1);
+                self.edges[t1e2 ^ 1].set_next(t0e1);
 
-                self.edges[t0e0].vertex = self.edges[t1e1].vertex;
-                self.edges[t1e0].vertex = self.edges[t0e1].vertex;
+                self.edges[t0e0].set_vertex(v1);
+                self.edges[t1e0].set_vertex(v0);
 
-                self.edges[t0e0].next = t0e2;
-                self.edges[t1e0].next = t1e2;
-                self.edges[t0e1 ^ 1].next = t0e0;
-                self.edges[t1e1 ^ 1].next = t1e0;
+                self.edges[t0e0].set_next(t0e2);
+                self.edges[t1e0].set_next(t1e2);
+                self.edges[t0e1 ^ 1].set_next(t0e0);
+                self.edges[t1e1 ^ 1].set_next(t1e0);
 
-                self.edges[t0e2].face = t1;
-                self.edges[t1e2].face = t0;
+                self.edges[t0e2].set_face(t1);
+                self.edges[t1e2].set_face(t0);
 
-                self.faces[t0].edge = t0e1 ^ 1;
-                self.faces[t1].edge = t1e1 ^ 1;
+                self.faces[t0].set_edge(t0e1 ^ 1);
+                self.faces[t1].set_edge(t1e1 ^ 1);
 
                 self.legalize(t1e1);
                 self.legalize(t1e2);
@@ -399,12 +415,13 @@ impl<'a> Delaunator<'a> {
         #[cfg(not(feature = "use-rayon"))]
         dists.sort_unstable_by(|&(_, da), &(_, db)| da.partial_cmp(&db).unwrap());
 
-        for i in 3..dists.len() {
+        // Points cocircular with the seed triangle can sort ahead of its corners.
+        for i in 0..dists.len() {
             let new_point = dists[i].0;
             if new_point == i0
                 || new_point == i1
                 || new_point == i2
-                || self.nearly_equals(dists[i - 1].0, new_point)
+                || (i > 0 && self.nearly_equals(dists[i - 1].0, new_point))
             {
                 continue;
             };
@@ -416,28 +433,28 @@ impl<'a> Delaunator<'a> {
                 let mut current = edge;
                 let mut current_position;
 
-                let mut next = self.edges[current].next;
-                let mut next_vertex = self.edges[next].vertex;
+                let mut next = self.edges[current].next();
+                let mut next_vertex = self.edges[next].vertex();
                 let mut next_position = self.vertices[next_vertex]
-                    .position
+                    .position()
                     .left()
                     .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
-                let mut previous = self.edges[self.edges[current ^ 1].next ^ 1].next ^ 1;
+                let mut previous = self.edges[self.edges[current ^ 1].next() ^ 1].next() ^ 1;
 
                 let e = self.add_triangle(vertex, current, next);
                 self.legalize(e);
 
-                let new_edge = self.vertices[0].edge;
+                let new_edge = self.vertices[0].edge();
 
                 loop {
                     current = next;
                     current_position = next_position;
 
-                    next = self.edges[current].next;
-                    next_vertex = self.edges[next].vertex;
+                    next = self.edges[current].next();
+                    next_vertex = self.edges[next].vertex();
                     next_position = self.vertices[next_vertex]
-                        .position
+                        .position()
                         .left()
                         .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
@@ -445,81 +462,136 @@ impl<'a> Delaunator<'a> {
                         break;
                     };
 
-                    let edge_1 = self.edges[next ^ 1].next;
-                    let edge_2 = self.edges[current ^ 1].next;
-                    let face_1 = self.edges[next].face;
-                    let face_2 = self.edges[current].face;
+                    let edge_1 = self.edges[next ^ 1].next();
+                    let edge_2 = self.edges[current ^ 1].next();
+                    let face_1 = self.edges[next].face();
+                    let face_2 = self.edges[current].face();
 
-                    self.edges[new_edge].next = next;
+                    self.edges[new_edge].set_next(next);
 
-                    self.edges[edge_2].face = face_1;
-                    self.faces[face_2].edge = current ^ 1;
+                    self.edges[edge_2].set_face(face_1);
+                    self.faces[face_2].set_edge(current ^ 1);
 
-                    self.edges[current].vertex = vertex;
-                    self.edges[current ^ 1].vertex = self.edges[next].vertex;
+                    self.edges[current].set_vertex(vertex);
+                    self.edges[current ^ 1].set_vertex(next_vertex);
 
-                    self.edges[current].next = edge_1;
-                    self.edges[current ^ 1].next = new_edge ^ 1;
+                    self.edges[current].set_next(edge_1);
+                    self.edges[current ^ 1].set_next(new_edge ^ 1);
 
-                    self.edges[next].face = face_2;
-                    self.edges[next ^ 1].next = current;
-                    self.edges[edge_2 ^ 1].next = current ^ 1;
-                    self.edges[edge_1 ^ 1].next = edge_2;
+                    self.edges[next].set_face(face_2);
+                    self.edges[next ^ 1].set_next(current);
+                    self.edges[edge_2 ^ 1].set_next(current ^ 1);
+                    self.edges[edge_1 ^ 1].set_next(edge_2);
 
                     self.legalize(edge_1);
                 }
 
                 if walk_back {
                     let mut current = edge;
-                    let current_vertex = self.edges[current].vertex;
+                    let current_vertex = self.edges[current].vertex();
                     let mut current_position = self.vertices[current_vertex]
-                        .position
+                        .position()
                         .left()
                         .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
-                    let mut previous_vertex = self.edges[previous].vertex;
+                    let mut previous_vertex = self.edges[previous].vertex();
                     let mut previous_position = self.vertices[previous_vertex]
-                        .position
+                        .position()
                         .left()
                         .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
                     while !self.is_ccw(new_point, previous_position, current_position) {
-                        let edge_1 = self.edges[new_edge ^ 1].next;
-                        let edge_2 = self.edges[current ^ 1].next;
-                        let face_1 = self.edges[new_edge].face;
-                        let face_2 = self.edges[current].face;
+                        let edge_1 = self.edges[new_edge ^ 1].next();
+                        let edge_2 = self.edges[current ^ 1].next();
+                        let face_1 = self.edges[new_edge].face();
+                        let face_2 = self.edges[current].face();
 
-                        self.edges[previous].next = new_edge;
+                        self.edges[previous].set_next(new_edge);
 
-                        self.edges[edge_2].face = face_1;
-                        self.faces[face_2].edge = current ^ 1;
+                        self.edges[edge_2].set_face(face_1);
+                        self.faces[face_2].set_edge(current ^ 1);
 
-                        self.edges[current].vertex = self.edges[previous].vertex;
-                        self.edges[current ^ 1].vertex = vertex;
+                        self.edges[current].set_vertex(previous_vertex);
+                        self.edges[current ^ 1].set_vertex(vertex);
 
-                        self.edges[current].next = edge_1;
-                        self.edges[current ^ 1].next = previous ^ 1;
+                        self.edges[current].set_next(edge_1);
+                        self.edges[current ^ 1].set_next(previous ^ 1);
 
-                        self.edges[new_edge].face = face_2;
-                        self.edges[new_edge ^ 1].next = current;
-                        self.edges[edge_2 ^ 1].next = current ^ 1;
-                        self.edges[edge_1 ^ 1].next = edge_2;
+                        self.edges[new_edge].set_face(face_2);
+                        self.edges[new_edge ^ 1].set_next(current);
+                        self.edges[edge_2 ^ 1].set_next(current ^ 1);
+                        self.edges[edge_1 ^ 1].set_next(edge_2);
 
                         self.legalize(edge_2);
 
                         current = previous;
                         current_position = previous_position;
 
-                        previous = self.edges[self.edges[current ^ 1].next ^ 1].next ^ 1;
-                        previous_vertex = self.edges[previous].vertex;
+                        previous = self.edges[self.edges[current ^ 1].next() ^ 1].next() ^ 1;
+                        previous_vertex = self.edges[previous].vertex();
                         previous_position = self.vertices[previous_vertex]
-                            .position
+                            .position()
                             .left()
                             .unwrap_or_else(|| unsafe { unreachable_unchecked() });
                     }
                 }
             }
         }
+
+        #[cfg(feature = "validate")]
+        {
+            debug_assert_eq!(crate::validate::validate(self), Ok(()));
+            debug_assert_eq!(crate::validate::verify(self), Ok(()));
+        }
+
         Ok(())
     }
 }
+
+#[cfg(feature = "validate")]
+impl<'a, S: Float> Records for Delaunator<'a, S> {
+    #[inline]
+    fn half_edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[inline]
+    fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    #[inline]
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    #[inline]
+    fn point_count(&self) -> usize {
+        self.points.len()
+    }
+
+    #[inline]
+    fn half_edge(&self, id: usize) -> GraphEdge {
+        self.edges[id]
+    }
+
+    #[inline]
+    fn face_edge(&self, id: usize) -> usize {
+        self.faces[id].edge()
+    }
+
+    #[inline]
+    fn vertex_edge(&self, id: usize) -> usize {
+        self.vertices[id].edge()
+    }
+
+    #[inline]
+    fn vertex_point(&self, id: usize) -> Option<usize> {
+        self.vertices[id].position().left()
+    }
+
+    #[inline]
+    fn point(&self, id: usize) -> (f64, f64) {
+        (self.points[id].x.to_f64(), self.points[id].y.to_f64())
+    }
+}
@@ -1,14 +1,17 @@
 use crate::delaunator::Delaunator;
-use crate::Graph;
-use crate::Point;
+use crate::graph::{expand, Index};
+use crate::voronoi::{mapping, DELAUNAY_EDGE, DELAUNAY_FACE};
+use crate::{Attribute, Attributes, EdgeId, EditError, FaceId, Graph, Renumbering, VertexId};
+use crate::{Float, Point, Voronoi};
 use std::iter::FromIterator;
 use std::ops::Deref;
 
 #[derive(Debug)]
-pub struct Delaunay(Graph<()>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Delaunay<S: Float = f64>(Graph<(), S>);
 
-impl Deref for Delaunay {
-    type Target = Graph<()>;
+impl<S: Float> Deref for Delaunay<S> {
+    type Target = Graph<(), S>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -16,9 +19,21 @@ impl Deref for Delaunay {
     }
 }
 
-impl Delaunay {
+impl<S: Float> Delaunay<S> {
+    /// Most points `from` accepts: the up to six half-edges per point, the infinite vertex
+    /// included, must stay indexable, which limits `compact-index` builds to about 715
+    /// million points.
     #[inline]
-    pub fn from(points: Vec<Point>) -> Result<Delaunay, ()> {
+    pub fn max_points() -> usize {
+        expand(Index::MAX) / 6 - 1
+    }
+
+    #[allow(clippy::result_unit_err)]
+    #[inline]
+    pub fn from(points: Vec<Point<S>>) -> Result<Delaunay<S>, ()> {
+        if points.len() > Self::max_points() {
+            return Err(());
+        }
         let len = points.len() + 1;
 
         let mut edges = Vec::with_capacity(len * 6);
@@ -27,18 +42,217 @@ impl Delaunay {
 
         Delaunator::new(&points, &mut edges, &mut faces, &mut vertices).process()?;
 
-        Ok(Delaunay(Graph {
-            points,
-            edges,
-            faces,
-            vertices,
-        }))
+        Ok(Delaunay(Graph::new(points, edges, faces, vertices)))
+    }
+
+    // Wraps a graph read from elsewhere once it checks out as a triangulation.
+    #[inline]
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_graph(graph: Graph<(), S>) -> Result<Self, crate::ValidationError> {
+        graph.validate()?;
+        crate::validate::triangulation(&graph)?;
+        Ok(Delaunay(graph))
+    }
+
+    // Wraps a graph whose records already passed `validate` and `triangulation`.
+    #[inline]
+    pub(crate) fn from_checked(graph: Graph<(), S>) -> Self {
+        Delaunay(graph)
+    }
+
+    // Hands the input points back, for callers that triangulate the same buffer repeatedly.
+    #[inline]
+    pub(crate) fn into_points(self) -> Vec<Point<S>> {
+        self.0.points
+    }
+
+    /// Checks the empty-circumcircle property of every interior edge, returning the edges
+    /// that fail it. Edges of cocircular quads, where either diagonal would do, pass.
+    #[inline]
+    pub fn verify(&self) -> Result<(), Vec<EdgeId>> {
+        crate::validate::verify(&self.0)
+    }
+
+    /// Adds a vertex attribute from one value per finite vertex, in id order: vertex 1 takes
+    /// the first value. The infinite vertex 0 keeps `A::default()`.
+    #[inline]
+    pub fn insert_site_attribute<A: Attribute>(
+        &mut self,
+        name: &str,
+        values: Vec<A>,
+    ) -> Result<(), Vec<A>> {
+        if values.len() + 1 != self.vertex_count() {
+            return Err(values);
+        }
+
+        let column = std::iter::once(A::default())
+            .chain(values)
+            .collect::<Vec<_>>();
+        self.0
+            .vertex_attributes
+            .insert(name, column)
+            .map_err(|mut column| column.split_off(1))
+    }
+
+    /// Cell of `vertex` in `voronoi`, built from `self`. Merges never remove cells, so this
+    /// holds through `Voronoi::merge_coincident_vertices`.
+    #[inline]
+    pub fn voronoi_face(&self, vertex: VertexId) -> FaceId {
+        FaceId::new(vertex.index())
+    }
+
+    /// Circumcenter (or ray, for hull faces) of `face` in `voronoi`, built from `self`. Once
+    /// coincident vertices are merged, this is the vertex they were merged into: the one
+    /// where the cells of the three corners of `face` meet.
+    pub fn voronoi_vertex(&self, face: FaceId, voronoi: &Voronoi<S>) -> Option<VertexId> {
+        if mapping::<FaceId>(voronoi.vertex_attributes(), DELAUNAY_FACE).is_none() {
+            return Some(VertexId::new(face.index()));
+        }
+
+        let corners = self
+            .face(face)
+            .vertices()
+            .map(|vertex| self.voronoi_face(vertex.id()))
+            .collect::<Vec<_>>();
+        voronoi
+            .face(corners[0])
+            .vertices()
+            .find(|vertex| {
+                corners[1..]
+                    .iter()
+                    .all(|&corner| vertex.faces().any(|face| face.id() == corner))
+            })
+            .map(|vertex| vertex.id())
+    }
+
+    /// Edge of `voronoi`, built from `self`, on the bisector of `edge`, or `None` if merging
+    /// coincident vertices contracted it. Half-edges keep their direction: Voronoi half-edge
+    /// `h` of `voronoi_edge(e)` crosses Delaunay half-edge `h` of `e` from its left to its right.
+    pub fn voronoi_edge(&self, edge: EdgeId, voronoi: &Voronoi<S>) -> Option<EdgeId> {
+        if mapping::<EdgeId>(voronoi.edge_attributes(), DELAUNAY_EDGE).is_none() {
+            return Some(edge);
+        }
+
+        let cell = self.voronoi_face(self.edge(edge).origin().id());
+        voronoi
+            .face(cell)
+            .edges()
+            .map(|bisector| bisector.id())
+            .find(|&bisector| voronoi.delaunay_edge(bisector) == edge)
+    }
+}
+
+// Edits go through `Delaunay` so that it stays a triangulation with a single infinite vertex.
+// The `_legalized` variants also restore the empty-circumcircle property with edge flips.
+impl<S: Float> Delaunay<S> {
+    #[inline]
+    pub fn edge_attributes_mut(&mut self) -> &mut Attributes {
+        self.0.edge_attributes_mut()
+    }
+
+    #[inline]
+    pub fn face_attributes_mut(&mut self) -> &mut Attributes {
+        self.0.face_attributes_mut()
+    }
+
+    #[inline]
+    pub fn vertex_attributes_mut(&mut self) -> &mut Attributes {
+        self.0.vertex_attributes_mut()
+    }
+
+    /// See `Graph::flip_edge`. The flipped edge may not be locally Delaunay.
+    #[inline]
+    pub fn flip_edge(&mut self, id: EdgeId) -> Result<(), EditError> {
+        self.0.flip_edge(id)
+    }
+
+    /// See `Graph::split_edge`.
+    #[inline]
+    pub fn split_edge(&mut self, id: EdgeId, point: Point<S>) -> Result<VertexId, EditError> {
+        self.0.split_edge(id, point)
+    }
+
+    /// See `Graph::split_face`.
+    #[inline]
+    pub fn split_face(&mut self, id: FaceId, point: Point<S>) -> Result<VertexId, EditError> {
+        self.0.split_face(id, point)
+    }
+
+    /// See `Graph::collapse_edge`.
+    #[inline]
+    pub fn collapse_edge(
+        &mut self,
+        id: EdgeId,
+        point: Point<S>,
+    ) -> Result<(VertexId, Renumbering), EditError> {
+        self.0.collapse_edge(id, point)
+    }
+
+    #[inline]
+    pub fn split_edge_legalized(
+        &mut self,
+        id: EdgeId,
+        point: Point<S>,
+    ) -> Result<VertexId, EditError> {
+        let vertex = self.split_edge(id, point)?;
+        self.legalize(vertex);
+        Ok(vertex)
+    }
+
+    #[inline]
+    pub fn split_face_legalized(
+        &mut self,
+        id: FaceId,
+        point: Point<S>,
+    ) -> Result<VertexId, EditError> {
+        let vertex = self.split_face(id, point)?;
+        self.legalize(vertex);
+        Ok(vertex)
+    }
+
+    #[inline]
+    pub fn collapse_edge_legalized(
+        &mut self,
+        id: EdgeId,
+        point: Point<S>,
+    ) -> Result<(VertexId, Renumbering), EditError> {
+        let (vertex, renumbering) = self.collapse_edge(id, point)?;
+        self.legalize(vertex);
+        Ok((vertex, renumbering))
+    }
+
+    // Flips illegal edges around `vertex` until none is left. Both the spokes and the link
+    // of the vertex are checked: a split only disturbs the link, but a collapse moves the
+    // vertex, which can break its spokes too.
+    fn legalize(&mut self, vertex: VertexId) {
+        let graph = &mut self.0;
+        let spokes = graph.outgoing_of(vertex.index());
+        let mut stack = spokes
+            .iter()
+            .map(|&edge| graph.next_of(edge))
+            .chain(spokes.iter().copied())
+            .collect::<Vec<_>>();
+
+        while let Some(edge) = stack.pop() {
+            if graph.is_illegal(edge) {
+                let around = [
+                    graph.next_of(edge),
+                    graph.prev_of(edge),
+                    graph.next_of(edge ^ 1),
+                    graph.prev_of(edge ^ 1),
+                ];
+
+                if graph.flip_edge(EdgeId::new(edge >> 1)).is_ok() {
+                    stack.extend_from_slice(&around);
+                }
+            }
+        }
     }
 }
 
-impl FromIterator<Point> for Result<Delaunay, ()> {
+impl<S: Float> FromIterator<Point<S>> for Result<Delaunay<S>, ()> {
     #[inline]
-    fn from_iter<I: IntoIterator<Item = Point>>(iter: I) -> Self {
+    fn from_iter<I: IntoIterator<Item = Point<S>>>(iter: I) -> Self {
         let points = iter.into_iter().collect::<Vec<_>>();
         Delaunay::from(points)
     }
@@ -0,0 +1,263 @@
+//! Point generators for structured and degenerate inputs. The random ones are each a
+//! `Distribution<Point>`; [`JitteredGrid`] also hands out its cells in order, and the
+//! [`Halton`] and [`Sobol`] sequences are iterators with their points also coming by index.
+
+use crate::{Float, Point};
+use rand::distributions::Distribution;
+use rand::Rng;
+use std::f64::consts::TAU;
+use std::marker::PhantomData;
+
+#[inline]
+fn point<S: Float>(x: f64, y: f64) -> Point<S> {
+    Point::new(S::from_f64(x), S::from_f64(y))
+}
+
+#[inline]
+fn normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u = 1.0 - rng.gen::<f64>();
+    (-2.0 * u.ln()).sqrt() * (TAU * rng.gen::<f64>()).cos()
+}
+
+/// Cell centres of a `columns` by `rows` grid over the unit square, each moved by up to
+/// `jitter` cell widths. Sampling picks a cell at random; [`points`](JitteredGrid::points)
+/// visits each of them once.
+#[derive(Clone, Debug)]
+pub struct JitteredGrid {
+    columns: usize,
+    rows: usize,
+    jitter: f64,
+}
+
+impl JitteredGrid {
+    #[inline]
+    pub fn new(columns: usize, rows: usize, jitter: f64) -> Self {
+        JitteredGrid {
+            columns: columns.max(1),
+            rows: rows.max(1),
+            jitter,
+        }
+    }
+
+    /// Number of cells, at least one.
+    #[inline]
+    pub fn cell_count(&self) -> usize {
+        self.columns * self.rows
+    }
+
+    /// Point of cell `index`, counted row by row from the bottom left.
+    #[inline]
+    pub fn point<S: Float, R: Rng + ?Sized>(&self, index: usize, rng: &mut R) -> Point<S> {
+        let (column, row) = (index % self.columns, index / self.columns % self.rows);
+        let dx = self.jitter * (rng.gen::<f64>() - 0.5);
+        let dy = self.jitter * (rng.gen::<f64>() - 0.5);
+
+        point(
+            (column as f64 + 0.5 + dx) / self.columns as f64,
+            (row as f64 + 0.5 + dy) / self.rows as f64,
+        )
+    }
+
+    /// One point per cell, row by row.
+    #[inline]
+    pub fn points<'a, S: Float, R: Rng + ?Sized>(
+        &'a self,
+        rng: &'a mut R,
+    ) -> impl Iterator<Item = Point<S>> + 'a {
+        (0..self.cell_count()).map(move |index| self.point(index, rng))
+    }
+}
+
+impl<S: Float> Distribution<Point<S>> for JitteredGrid {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<S> {
+        let index = rng.gen_range(0..self.cell_count());
+        self.point(index, rng)
+    }
+}
+
+#[inline]
+fn radical_inverse(mut index: usize, base: usize) -> f64 {
+    let (mut value, mut scale) = (0.0, 1.0);
+    while index > 0 {
+        scale /= base as f64;
+        value += (index % base) as f64 * scale;
+        index /= base;
+    }
+    value
+}
+
+/// Halton sequence in bases 2 and 3, iterated from the point after the origin.
+#[derive(Clone, Debug)]
+pub struct Halton<S: Float = f64> {
+    index: usize,
+    marker: PhantomData<S>,
+}
+
+impl<S: Float> Halton<S> {
+    #[inline]
+    pub fn new() -> Self {
+        Halton {
+            index: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Point `index` of the sequence, the origin being point 0.
+    #[inline]
+    pub fn point(index: usize) -> Point<S> {
+        point(radical_inverse(index, 2), radical_inverse(index, 3))
+    }
+}
+
+impl<S: Float> Default for Halton<S> {
+    #[inline]
+    fn default() -> Self {
+        Halton::new()
+    }
+}
+
+impl<S: Float> Iterator for Halton<S> {
+    type Item = Point<S>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Point<S>> {
+        self.index = self.index.wrapping_add(1);
+        Some(Halton::point(self.index))
+    }
+}
+
+/// First two dimensions of the Sobol sequence, iterated from the point after the origin.
+#[derive(Clone, Debug)]
+pub struct Sobol<S: Float = f64> {
+    index: usize,
+    marker: PhantomData<S>,
+}
+
+impl<S: Float> Sobol<S> {
+    #[inline]
+    pub fn new() -> Self {
+        Sobol {
+            index: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Point `index` of the sequence, the origin being point 0.
+    #[inline]
+    pub fn point(index: usize) -> Point<S> {
+        let (mut x, mut y) = (0u32, 0u32);
+        let mut direction = 1u32 << 31;
+        for bit in 0..32 {
+            if (index >> bit) & 1 == 1 {
+                x ^= 1 << (31 - bit);
+                y ^= direction;
+            }
+            direction ^= direction >> 1;
+        }
+
+        let scale = 1.0 / 4_294_967_296.0;
+        point(f64::from(x) * scale, f64::from(y) * scale)
+    }
+}
+
+impl<S: Float> Default for Sobol<S> {
+    #[inline]
+    fn default() -> Self {
+        Sobol::new()
+    }
+}
+
+impl<S: Float> Iterator for Sobol<S> {
+    type Item = Point<S>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Point<S>> {
+        self.index = self.index.wrapping_add(1);
+        Some(Sobol::point(self.index))
+    }
+}
+
+/// Normally distributed points around centres picked uniformly at random.
+#[derive(Clone, Debug)]
+pub struct GaussianClusters<S: Float = f64> {
+    centers: Vec<Point<S>>,
+    deviation: S,
+}
+
+impl<S: Float> GaussianClusters<S> {
+    #[inline]
+    pub fn new(centers: Vec<Point<S>>, deviation: S) -> Self {
+        assert!(!centers.is_empty(), "no cluster centres");
+        GaussianClusters { centers, deviation }
+    }
+}
+
+impl<S: Float> Distribution<Point<S>> for GaussianClusters<S> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<S> {
+        let center = self.centers[rng.gen_range(0..self.centers.len())];
+        let deviation = self.deviation.to_f64();
+
+        point(
+            center.x.to_f64() + deviation * normal(rng),
+            center.y.to_f64() + deviation * normal(rng),
+        )
+    }
+}
+
+/// Points on a circle, so every four of them are cocircular.
+#[derive(Clone, Debug)]
+pub struct Circle<S: Float = f64> {
+    center: Point<S>,
+    radius: S,
+}
+
+impl<S: Float> Circle<S> {
+    #[inline]
+    pub fn new(center: Point<S>, radius: S) -> Self {
+        Circle { center, radius }
+    }
+}
+
+impl<S: Float> Distribution<Point<S>> for Circle<S> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<S> {
+        let angle = TAU * rng.gen::<f64>();
+        let radius = self.radius.to_f64();
+
+        point(
+            self.center.x.to_f64() + radius * angle.cos(),
+            self.center.y.to_f64() + radius * angle.sin(),
+        )
+    }
+}
+
+/// Points on the segment between two ends, moved off it by up to `noise` either side.
+#[derive(Clone, Debug)]
+pub struct NearCollinear<S: Float = f64> {
+    from: Point<S>,
+    to: Point<S>,
+    noise: S,
+}
+
+impl<S: Float> NearCollinear<S> {
+    #[inline]
+    pub fn new(from: Point<S>, to: Point<S>, noise: S) -> Self {
+        NearCollinear { from, to, noise }
+    }
+}
+
+impl<S: Float> Distribution<Point<S>> for NearCollinear<S> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point<S> {
+        let (x0, y0) = (self.from.x.to_f64(), self.from.y.to_f64());
+        let (dx, dy) = (self.to.x.to_f64() - x0, self.to.y.to_f64() - y0);
+        let length = dx.hypot(dy).max(f64::MIN_POSITIVE);
+
+        let t = rng.gen::<f64>();
+        let offset = self.noise.to_f64() * (2.0 * rng.gen::<f64>() - 1.0) / length;
+
+        point(x0 + t * dx - offset * dy, y0 + t * dy + offset * dx)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::graph::{Graph, GraphEdge, GraphFace, GraphVertex};
+use crate::{Face, Float, Point};
+use either::{Either, Left, Right};
+use std::fmt::Debug;
+
+impl<T: Debug + Copy, S: Float> Graph<T, S> {
+    /// Builds the dual graph, placing the dual vertex of each face with `position`.
+    ///
+    /// Ids carry over: dual vertex `f` belongs to face `f`, dual face `v` surrounds vertex `v`
+    /// and dual half-edge `e` crosses half-edge `e`, running from its left face to its right
+    /// face. Edge attributes stay on their edges, vertex attributes become face attributes
+    /// and face attributes become vertex attributes.
+    pub fn dual<U, F>(&self, mut position: F) -> Graph<U, S>
+    where
+        U: Debug + Copy,
+        F: FnMut(Face<'_, T, S>) -> Either<Point<S>, U>,
+    {
+        let edges = (0..self.edges.len())
+            .map(|e| {
+                GraphEdge::new(
+                    self.edges[e ^ 1].face(),
+                    self.prev_of(e ^ 1) ^ 1,
+                    self.edges[e].vertex(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let faces = self
+            .vertices
+            .iter()
+            .map(|v| GraphFace::new(v.edge()))
+            .collect::<Vec<_>>();
+
+        let mut points = Vec::with_capacity(self.faces.len());
+        let vertices = (0..self.faces.len())
+            .map(|f| {
+                let position = match position(self.face_at(f)) {
+                    Left(point) => {
+                        points.push(point);
+                        Left(points.len() - 1)
+                    }
+                    Right(value) => Right(value),
+                };
+                GraphVertex::new(self.faces[f].edge() ^ 1, position)
+            })
+            .collect::<Vec<_>>();
+
+        Graph {
+            points,
+            edges,
+            faces,
+            vertices,
+            edge_attributes: self.edge_attributes.clone(),
+            face_attributes: self.vertex_attributes.clone(),
+            vertex_attributes: self.face_attributes.clone(),
+        }
+    }
+}
@@ -0,0 +1,661 @@
+use crate::graph::{fits_index, Graph, GraphEdge, GraphFace, GraphVertex};
+use crate::{EdgeId, FaceId, Float, Point, VertexId};
+use either::Left;
+use std::fmt::{self, Debug, Display};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EditError {
+    NotTriangle,
+    InfiniteVertex,
+    Degenerate,
+    Capacity,
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::NotTriangle => write!(f, "adjacent face is not a triangle"),
+            EditError::InfiniteVertex => write!(f, "edit touches the infinite vertex"),
+            EditError::Degenerate => write!(f, "edit would make the graph degenerate"),
+            EditError::Capacity => write!(f, "graph would outgrow its index type"),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+/// Ids changed by an edit that removes elements. Each removed element leaves its slot to the
+/// last one of its kind, as `Vec::swap_remove` does, so that element gets the removed id.
+/// Ids not listed here still name the same element.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Renumbering {
+    edges: Vec<(usize, Option<usize>)>,
+    faces: Vec<(usize, Option<usize>)>,
+    vertices: Vec<(usize, Option<usize>)>,
+}
+
+impl Renumbering {
+    /// New id of the edge that had id `id`, or `None` if it was removed.
+    #[inline]
+    pub fn edge(&self, id: EdgeId) -> Option<EdgeId> {
+        lookup(&self.edges, id.index()).map(EdgeId::new)
+    }
+
+    /// New id of the face that had id `id`, or `None` if it was removed.
+    #[inline]
+    pub fn face(&self, id: FaceId) -> Option<FaceId> {
+        lookup(&self.faces, id.index()).map(FaceId::new)
+    }
+
+    /// New id of the vertex that had id `id`, or `None` if it was removed.
+    #[inline]
+    pub fn vertex(&self, id: VertexId) -> Option<VertexId> {
+        lookup(&self.vertices, id.index()).map(VertexId::new)
+    }
+
+    /// Whether the edit kept every id.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty() && self.faces.is_empty() && self.vertices.is_empty()
+    }
+}
+
+#[inline]
+fn lookup(moves: &[(usize, Option<usize>)], id: usize) -> Option<usize> {
+    moves
+        .iter()
+        .find(|&&(old, _)| old == id)
+        .map_or(Some(id), |&(_, new)| new)
+}
+
+// Records that the element now in `slot` was removed and the one in `last` took its place.
+fn record(moves: &mut Vec<(usize, Option<usize>)>, slot: usize, last: usize) {
+    match moves.iter_mut().find(|(_, new)| *new == Some(slot)) {
+        Some(entry) => entry.1 = None,
+        None => moves.push((slot, None)),
+    }
+    if slot != last {
+        match moves.iter_mut().find(|(_, new)| *new == Some(last)) {
+            Some(entry) => entry.1 = Some(slot),
+            None => moves.push((last, Some(slot))),
+        }
+    }
+}
+
+impl<T: Debug + Copy, S: Float> Graph<T, S> {
+    #[inline]
+    pub(crate) fn next_of(&self, edge: usize) -> usize {
+        self.edges[edge].next() ^ 1
+    }
+
+    #[inline]
+    pub(crate) fn prev_of(&self, edge: usize) -> usize {
+        let mut current = edge;
+        loop {
+            let next = self.next_of(current);
+            if next == edge {
+                break current;
+            }
+            current = next;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn origin_of(&self, edge: usize) -> usize {
+        self.edges[edge].vertex()
+    }
+
+    #[inline]
+    pub(crate) fn left_of(&self, edge: usize) -> usize {
+        self.edges[edge ^ 1].face()
+    }
+
+    #[inline]
+    pub(crate) fn is_triangle(&self, edge: usize) -> bool {
+        self.next_of(self.next_of(self.next_of(edge))) == edge
+    }
+
+    #[inline]
+    pub(crate) fn position_of(&self, vertex: usize) -> Option<&Point<S>> {
+        self.vertices[vertex]
+            .position()
+            .left()
+            .map(|id| &self.points[id])
+    }
+
+    #[inline]
+    pub(crate) fn outgoing_of(&self, vertex: usize) -> Vec<usize> {
+        let first = self.vertices[vertex].edge() ^ 1;
+        let mut edges = vec![first];
+        loop {
+            let next = self.next_of(edges[edges.len() - 1] ^ 1);
+            if next == first {
+                break edges;
+            }
+            edges.push(next);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn cycle_of(&self, edge: usize) -> Vec<usize> {
+        let mut edges = vec![edge];
+        loop {
+            let next = self.next_of(edges[edges.len() - 1]);
+            if next == edge {
+                break edges;
+            }
+            edges.push(next);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_illegal(&self, edge: usize) -> bool {
+        crate::validate::is_illegal(self, edge)
+    }
+
+    #[inline]
+    fn set_next_of(&mut self, edge: usize, next: usize) {
+        self.edges[edge].set_next(next ^ 1);
+    }
+
+    #[inline]
+    fn set_left_of(&mut self, edge: usize, face: usize) {
+        self.edges[edge ^ 1].set_face(face);
+    }
+
+    #[inline]
+    fn set_cycle(&mut self, face: usize, cycle: &[usize]) {
+        for (i, &edge) in cycle.iter().enumerate() {
+            self.set_next_of(edge, cycle[(i + 1) % cycle.len()]);
+            self.set_left_of(edge, face);
+        }
+        self.faces[face].set_edge(cycle[0]);
+    }
+
+    #[inline]
+    fn is_linked(&self, v0: usize, v1: usize) -> bool {
+        self.outgoing_of(v0)
+            .into_iter()
+            .any(|edge| self.origin_of(edge ^ 1) == v1)
+    }
+
+    // Checks that `vertices`, `edges` and `faces` more elements can still be indexed.
+    #[inline]
+    fn reserve(&self, vertices: usize, edges: usize, faces: usize) -> Result<(), EditError> {
+        let fits = fits_index(self.vertices.len() + vertices)
+            && fits_index(self.points.len() + vertices)
+            && fits_index(self.edges.len() + 2 * edges)
+            && fits_index(self.faces.len() + faces);
+        if fits {
+            Ok(())
+        } else {
+            Err(EditError::Capacity)
+        }
+    }
+
+    // Whether the finite face of `edge` stays counter-clockwise with `point` inserted on it,
+    // split into triangles at the corner opposite if it is a triangle.
+    fn keeps_orientation(&self, edge: usize, point: &Point<S>) -> bool {
+        let corners = self
+            .cycle_of(edge)
+            .into_iter()
+            .map(|edge| self.position_of(self.origin_of(edge)))
+            .collect::<Option<Vec<_>>>();
+        match corners.as_deref() {
+            None => true,
+            Some([a, b, c]) => Point::is_ccw(a, point, c) && Point::is_ccw(point, b, c),
+            Some(corners) => {
+                let mut corners = corners.iter().map(|&&p| p).collect::<Vec<_>>();
+                corners.insert(1, *point);
+                crate::cell::signed_area(&corners) > 0.0
+            }
+        }
+    }
+
+    #[inline]
+    fn push_vertex(&mut self, point: Point<S>) -> usize {
+        let vertex = self.vertices.len();
+        self.vertices
+            .push(GraphVertex::new(0, Left(self.points.len())));
+        self.points.push(point);
+        self.vertex_attributes.push_default();
+        vertex
+    }
+
+    #[inline]
+    fn push_edge(&mut self, origin: usize, destination: usize, like: Option<usize>) -> usize {
+        let edge = self.edges.len();
+        self.edges.push(GraphEdge::new(origin, 0, 0));
+        self.edges.push(GraphEdge::new(destination, 0, 0));
+        match like {
+            Some(like) => self.edge_attributes.push_copy(like >> 1),
+            None => self.edge_attributes.push_default(),
+        }
+        edge
+    }
+
+    #[inline]
+    fn push_face(&mut self, edge: usize, like: usize) -> usize {
+        let face = self.faces.len();
+        self.faces.push(GraphFace::new(edge));
+        self.face_attributes.push_copy(like);
+        face
+    }
+
+    // Splits the face of `e0` with a new edge from the origin of `e1` to the origin of `e0`.
+    fn insert_diagonal(&mut self, e0: usize, e1: usize) -> usize {
+        let face = self.left_of(e0);
+        let p0 = self.prev_of(e0);
+        let p1 = self.prev_of(e1);
+        let diagonal = self.push_edge(self.origin_of(e1), self.origin_of(e0), None);
+
+        self.set_next_of(p1, diagonal);
+        self.set_next_of(diagonal, e0);
+        self.set_left_of(diagonal, face);
+        self.faces[face].set_edge(e0);
+
+        let new_face = self.push_face(e1, face);
+        self.set_next_of(p0, diagonal ^ 1);
+        self.set_next_of(diagonal ^ 1, e1);
+        for edge in self.cycle_of(e1) {
+            self.set_left_of(edge, new_face);
+        }
+
+        diagonal
+    }
+
+    fn remove_edge(&mut self, edge: usize, renumbering: &mut Renumbering) {
+        let last = self.edges.len() - 2;
+        let edge = edge & !1;
+        record(&mut renumbering.edges, edge >> 1, last >> 1);
+
+        if edge != last {
+            let moved = |id: usize| {
+                if id & !1 == last {
+                    id - last + edge
+                } else {
+                    id
+                }
+            };
+            let mut nexts = Vec::with_capacity(2);
+
+            for from in [last, last | 1] {
+                let to = from - last + edge;
+                nexts.push((moved(self.prev_of(from ^ 1)), to));
+
+                let face = self.left_of(from);
+                if self.faces[face].edge() == from {
+                    self.faces[face].set_edge(to);
+                }
+
+                let vertex = self.origin_of(from ^ 1);
+                if self.vertices[vertex].edge() == from {
+                    self.vertices[vertex].set_edge(to);
+                }
+            }
+
+            self.edges.swap_remove(edge | 1);
+            self.edges.swap_remove(edge);
+            for (prev, to) in nexts {
+                self.edges[prev].set_next(to);
+            }
+        } else {
+            self.edges.truncate(last);
+        }
+
+        self.edge_attributes.swap_remove(edge >> 1);
+    }
+
+    fn remove_face(&mut self, face: usize, renumbering: &mut Renumbering) {
+        let last = self.faces.len() - 1;
+        record(&mut renumbering.faces, face, last);
+
+        if face != last {
+            for edge in self.cycle_of(self.faces[last].edge()) {
+                self.set_left_of(edge, face);
+            }
+        }
+
+        self.faces.swap_remove(face);
+        self.face_attributes.swap_remove(face);
+    }
+
+    fn remove_vertex(&mut self, vertex: usize, renumbering: &mut Renumbering) {
+        let last = self.vertices.len() - 1;
+        record(&mut renumbering.vertices, vertex, last);
+
+        if vertex != last {
+            for edge in self.outgoing_of(last) {
+                self.edges[edge].set_vertex(vertex);
+            }
+        }
+
+        self.vertices.swap_remove(vertex);
+        self.vertex_attributes.swap_remove(vertex);
+    }
+
+    // Removes a point no vertex uses any more, moving the last point into its slot.
+    fn remove_point(&mut self, point: usize) {
+        let last = self.points.len() - 1;
+
+        if point != last {
+            let owner = self
+                .vertices
+                .iter()
+                .rposition(|vertex| vertex.position().left() == Some(last));
+            if let Some(owner) = owner {
+                self.vertices[owner].set_position(Left(point));
+            }
+        }
+
+        self.points.swap_remove(point);
+    }
+
+    /// Replaces an edge with the other diagonal of the quad formed by its two triangles.
+    /// Fails with `NotTriangle` if either side is not a triangle, with `InfiniteVertex` if a
+    /// corner of the quad is infinite, which would join the infinite vertex to an interior
+    /// vertex or take a vertex off the hull, and with `Degenerate` if both sides are the same
+    /// face, the opposite corners are already linked, or the quad is not strictly convex.
+    pub fn flip_edge(&mut self, id: EdgeId) -> Result<(), EditError> {
+        let e0 = id.half_edges().0.index();
+        let e1 = e0 ^ 1;
+
+        if !self.is_triangle(e0) || !self.is_triangle(e1) {
+            return Err(EditError::NotTriangle);
+        }
+
+        let n0 = self.next_of(e0);
+        let p0 = self.next_of(n0);
+        let n1 = self.next_of(e1);
+        let p1 = self.next_of(n1);
+
+        let a = self.origin_of(e0);
+        let b = self.origin_of(e1);
+        let c = self.origin_of(p0);
+        let d = self.origin_of(p1);
+        let f0 = self.left_of(e0);
+        let f1 = self.left_of(e1);
+
+        let position = |vertex| self.position_of(vertex).ok_or(EditError::InfiniteVertex);
+        let (pa, pb, pc, pd) = (position(a)?, position(b)?, position(c)?, position(d)?);
+
+        if f0 == f1 || c == d || self.is_linked(c, d) {
+            return Err(EditError::Degenerate);
+        }
+        // Both new triangles must be counter-clockwise, that is the quad a, d, b, c must be
+        // strictly convex.
+        if !Point::is_ccw(pd, pc, pa) || !Point::is_ccw(pc, pd, pb) {
+            return Err(EditError::Degenerate);
+        }
+
+        self.edges[e0].set_vertex(d);
+        self.edges[e1].set_vertex(c);
+
+        self.set_cycle(f0, &[e0, p0, n1]);
+        self.set_cycle(f1, &[e1, p1, n0]);
+
+        self.vertices[a].set_edge(p0);
+        self.vertices[b].set_edge(p1);
+
+        Ok(())
+    }
+
+    /// Inserts a vertex at `point` on an edge and joins it to the opposite corner of the
+    /// triangles on either side. The point must leave every finite face counter-clockwise.
+    pub fn split_edge(&mut self, id: EdgeId, point: Point<S>) -> Result<VertexId, EditError> {
+        let e0 = id.half_edges().0.index();
+        let e1 = e0 ^ 1;
+        if self.position_of(self.origin_of(e0)).is_none()
+            || self.position_of(self.origin_of(e1)).is_none()
+        {
+            return Err(EditError::InfiniteVertex);
+        }
+        if !self.keeps_orientation(e0, &point) || !self.keeps_orientation(e1, &point) {
+            return Err(EditError::Degenerate);
+        }
+        self.reserve(1, 3, 2)?;
+        let triangles = (self.is_triangle(e0), self.is_triangle(e1));
+
+        let b = self.origin_of(e1);
+        let next = self.next_of(e0);
+        let prev = self.prev_of(e1);
+
+        let vertex = self.push_vertex(point);
+        let edge = self.push_edge(vertex, b, Some(e0));
+
+        self.set_next_of(e0, edge);
+        self.set_next_of(edge, next);
+        self.set_left_of(edge, self.left_of(e0));
+
+        self.set_next_of(prev, edge ^ 1);
+        self.set_next_of(edge ^ 1, e1);
+        self.set_left_of(edge ^ 1, self.left_of(e1));
+
+        self.edges[e1].set_vertex(vertex);
+        self.vertices[vertex].set_edge(e0);
+        if self.vertices[b].edge() == e0 {
+            self.vertices[b].set_edge(edge);
+        }
+
+        if triangles.0 {
+            self.insert_diagonal(edge, self.prev_of(e0));
+        }
+        if triangles.1 {
+            self.insert_diagonal(e1, self.prev_of(edge ^ 1));
+        }
+
+        Ok(VertexId::new(vertex))
+    }
+
+    /// Inserts a vertex at `point` inside a finite face and fans it out to every corner. The
+    /// point must see each side of the face on its left.
+    pub fn split_face(&mut self, id: FaceId, point: Point<S>) -> Result<VertexId, EditError> {
+        let face = id.index();
+        let cycle = self.cycle_of(self.faces[face].edge());
+        let corners = cycle
+            .iter()
+            .map(|&edge| self.position_of(self.origin_of(edge)))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(EditError::InfiniteVertex)?;
+        let inside = (0..corners.len())
+            .all(|i| Point::is_ccw(corners[i], corners[(i + 1) % corners.len()], &point));
+        if !inside {
+            return Err(EditError::Degenerate);
+        }
+        self.reserve(1, cycle.len(), cycle.len() - 1)?;
+
+        let vertex = self.push_vertex(point);
+        let spokes = cycle
+            .iter()
+            .map(|&edge| self.push_edge(vertex, self.origin_of(edge), None))
+            .collect::<Vec<_>>();
+
+        for (i, &edge) in cycle.iter().enumerate() {
+            let f = if i == 0 {
+                face
+            } else {
+                self.push_face(edge, face)
+            };
+            let spoke = spokes[i];
+            let next_spoke = spokes[(i + 1) % spokes.len()];
+
+            self.set_cycle(f, &[edge, next_spoke ^ 1, spoke]);
+        }
+
+        self.vertices[vertex].set_edge(spokes[0] ^ 1);
+
+        Ok(VertexId::new(vertex))
+    }
+
+    /// Merges both ends of an edge into one vertex at `point`, which takes over the point of
+    /// the origin. The edge, the destination with its point, the two triangles and one side of
+    /// each are removed, and the last elements move into their slots: the returned
+    /// `Renumbering` maps every old id to its new one. Flips and splits only append, so ids
+    /// held across them stay valid.
+    pub fn collapse_edge(
+        &mut self,
+        id: EdgeId,
+        point: Point<S>,
+    ) -> Result<(VertexId, Renumbering), EditError> {
+        let e0 = id.half_edges().0.index();
+        let e1 = e0 ^ 1;
+        let a = self.origin_of(e0);
+        let b = self.origin_of(e1);
+
+        if self.vertices[a].position().is_right() || self.vertices[b].position().is_right() {
+            return Err(EditError::InfiniteVertex);
+        }
+        if !self.is_triangle(e0) || !self.is_triangle(e1) {
+            return Err(EditError::NotTriangle);
+        }
+
+        let n0 = self.next_of(e0);
+        let p0 = self.next_of(n0);
+        let n1 = self.next_of(e1);
+        let p1 = self.next_of(n1);
+
+        let c = self.origin_of(p0);
+        let d = self.origin_of(p1);
+        let f0 = self.left_of(e0);
+        let f1 = self.left_of(e1);
+
+        if f0 == f1 || c == d {
+            return Err(EditError::Degenerate);
+        }
+
+        let around_a = self.outgoing_of(a);
+        let around_b = self.outgoing_of(b);
+        let shared = around_b
+            .iter()
+            .map(|&edge| self.origin_of(edge ^ 1))
+            .filter(|&v| v != c && v != d)
+            .any(|v| around_a.iter().any(|&edge| self.origin_of(edge ^ 1) == v));
+        if shared {
+            return Err(EditError::Degenerate);
+        }
+
+        // Every other triangle around either end must stay counter-clockwise at `point`.
+        let flips = around_a.iter().chain(&around_b).any(|&edge| {
+            let face = self.left_of(edge);
+            if face == f0 || face == f1 || !self.is_triangle(edge) {
+                return false;
+            }
+            match (
+                self.position_of(self.origin_of(self.next_of(edge))),
+                self.position_of(self.origin_of(self.prev_of(edge))),
+            ) {
+                (Some(p1), Some(p2)) => !Point::is_ccw(&point, p1, p2),
+                _ => false,
+            }
+        });
+        if flips {
+            return Err(EditError::Degenerate);
+        }
+
+        // n0 (b -> c) collapses onto p0 (c -> a), p1 (d -> b) onto n1 (a -> d).
+        let removed = [(n0 ^ 1, p0), (p1 ^ 1, n1)];
+        let replace = |edge: usize| {
+            removed
+                .iter()
+                .find(|&&(old, _)| old == edge)
+                .map_or(edge, |&(_, new)| new)
+        };
+        let links = removed
+            .iter()
+            .map(|&(old, new)| {
+                (
+                    new,
+                    replace(self.prev_of(old)),
+                    replace(self.next_of(old)),
+                    self.left_of(old),
+                    old,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for (new, prev, next, face, old) in links {
+            self.set_next_of(prev, new);
+            self.set_next_of(new, next);
+            self.set_left_of(new, face);
+            if self.faces[face].edge() == old {
+                self.faces[face].set_edge(new);
+            }
+        }
+
+        for edge in around_b {
+            self.edges[edge].set_vertex(a);
+        }
+
+        let slot = self.vertices[a].position().left().unwrap();
+        self.vertices[a].set_edge(p0);
+        self.points[slot] = point;
+        if self.vertices[c].edge() == n0 {
+            self.vertices[c].set_edge(p0 ^ 1);
+        }
+        if self.vertices[d].edge() == p1 ^ 1 {
+            self.vertices[d].set_edge(n1);
+        }
+
+        let mut renumbering = Renumbering::default();
+        let mut edges = [e0, n0, p1];
+        edges.sort_unstable_by(|l, r| r.cmp(l));
+        for edge in edges {
+            self.remove_edge(edge, &mut renumbering);
+        }
+
+        self.remove_face(f0.max(f1), &mut renumbering);
+        self.remove_face(f0.min(f1), &mut renumbering);
+
+        if let Left(slot) = self.vertices[b].position() {
+            self.remove_point(slot);
+        }
+        self.remove_vertex(b, &mut renumbering);
+
+        let vertex = renumbering.vertex(VertexId::new(a)).unwrap();
+        Ok((vertex, renumbering))
+    }
+
+    // Merges the destination of `edge` into its origin, keeping the origin's position. Both
+    // sides must have at least four edges so that no face degenerates.
+    pub(crate) fn contract_edge(&mut self, edge: usize) -> Result<usize, EditError> {
+        let (e0, e1) = (edge & !1, edge | 1);
+        let a = self.origin_of(e0);
+        let b = self.origin_of(e1);
+        let f0 = self.left_of(e0);
+        let f1 = self.left_of(e1);
+
+        if a == b || f0 == f1 {
+            return Err(EditError::Degenerate);
+        }
+        if self.is_triangle(e0) || self.is_triangle(e1) {
+            return Err(EditError::NotTriangle);
+        }
+
+        let around_b = self.outgoing_of(b);
+        let (p0, n0) = (self.prev_of(e0), self.next_of(e0));
+        let (p1, n1) = (self.prev_of(e1), self.next_of(e1));
+
+        self.set_next_of(p0, n0);
+        self.set_next_of(p1, n1);
+        if self.faces[f0].edge() == e0 {
+            self.faces[f0].set_edge(n0);
+        }
+        if self.faces[f1].edge() == e1 {
+            self.faces[f1].set_edge(n1);
+        }
+
+        for edge in around_b.into_iter().filter(|&edge| edge != e1) {
+            self.edges[edge].set_vertex(a);
+        }
+        if self.vertices[a].edge() == e1 {
+            self.vertices[a].set_edge(p0);
+        }
+
+        let mut renumbering = Renumbering::default();
+        self.remove_edge(e0, &mut renumbering);
+        let last = self.vertices.len() - 1;
+        self.remove_vertex(b, &mut renumbering);
+
+        Ok(if a == last { b } else { a })
+    }
+}
@@ -0,0 +1,161 @@
+use crate::graph::{Edge, Face};
+use crate::{Float, Point, Vector};
+use std::f64::consts::PI;
+use std::fmt::Debug;
+
+#[inline]
+fn coords<S: Float>(point: &Point<S>) -> (f64, f64) {
+    (point.x.to_f64(), point.y.to_f64())
+}
+
+#[inline]
+fn point<S: Float>(x: f64, y: f64) -> Point<S> {
+    Point::new(S::from_f64(x), S::from_f64(y))
+}
+
+#[inline]
+fn distance(p0: (f64, f64), p1: (f64, f64)) -> f64 {
+    (p1.0 - p0.0).hypot(p1.1 - p0.1)
+}
+
+// Measures of faces and edges are computed in f64 and are `None` whenever an element touches
+// a vertex without position, such as the infinite vertex 0 or a Voronoi ray, so hull faces
+// never get a finite-looking answer.
+impl<'a, T: Debug + Copy, S: Float> Face<'a, T, S> {
+    /// Corners in counter-clockwise order.
+    #[inline]
+    pub fn points(&self) -> Option<Vec<Point<S>>> {
+        self.vertices()
+            .map(|vertex| vertex.position().left())
+            .collect()
+    }
+
+    #[inline]
+    fn triangle(&self) -> Option<[Point<S>; 3]> {
+        match self.points()?[..] {
+            [p0, p1, p2] => Some([p0, p1, p2]),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn area(&self) -> Option<S> {
+        Some(S::from_f64(crate::cell::signed_area(&self.points()?)))
+    }
+
+    #[inline]
+    pub fn centroid(&self) -> Option<Point<S>> {
+        Some(crate::cell::centroid(&self.points()?))
+    }
+
+    /// Centre of the circle through the corners of a triangle, `None` for other polygons and
+    /// collinear corners.
+    #[inline]
+    pub fn circumcenter(&self) -> Option<Point<S>> {
+        let [p0, p1, p2] = self.triangle()?;
+        Point::robust_circumcenter(&p0, &p1, &p2)
+    }
+
+    #[inline]
+    pub fn circumradius(&self) -> Option<S> {
+        let center = self.circumcenter()?;
+        let corner = self.vertices().next()?.position().left()?;
+        Some(S::from_f64(distance(coords(&center), coords(&corner))))
+    }
+
+    /// Centre and radius of the circle inscribed in a triangle, `None` for other polygons and
+    /// collinear corners.
+    pub fn incircle(&self) -> Option<(Point<S>, S)> {
+        let [p0, p1, p2] = self.triangle()?;
+        let (a, b, c) = (coords(&p0), coords(&p1), coords(&p2));
+        let (la, lb, lc) = (distance(b, c), distance(c, a), distance(a, b));
+        let perimeter = la + lb + lc;
+        let area = crate::cell::signed_area(&[p0, p1, p2]).abs();
+        if perimeter <= 0.0 || area <= 0.0 {
+            return None;
+        }
+
+        let x = (la * a.0 + lb * b.0 + lc * c.0) / perimeter;
+        let y = (la * a.1 + lb * b.1 + lc * c.1) / perimeter;
+        Some((point(x, y), S::from_f64(2.0 * area / perimeter)))
+    }
+
+    /// Interior angle in radians at each corner, in the order of [`vertices`](Self::vertices).
+    pub fn angles(&self) -> Option<Vec<S>> {
+        let points = self.points()?;
+        let len = points.len();
+        Some(
+            (0..len)
+                .map(|i| {
+                    let (x0, y0) = coords(&points[(i + len - 1) % len]);
+                    let (x1, y1) = coords(&points[i]);
+                    let (x2, y2) = coords(&points[(i + 1) % len]);
+                    let (ux, uy, vx, vy) = (x0 - x1, y0 - y1, x2 - x1, y2 - y1);
+
+                    // Counter-clockwise from the outgoing to the incoming side.
+                    let angle = (vx * uy - vy * ux).atan2(vx * ux + vy * uy);
+                    S::from_f64(if angle < 0.0 { angle + 2.0 * PI } else { angle })
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether `point` lies in the face or on its boundary. Faces are taken to be convex, as
+    /// Delaunay triangles and Voronoi cells are.
+    pub fn contains(&self, point: &Point<S>) -> Option<bool> {
+        let points = self.points()?;
+        let (x, y) = coords(point);
+        Some((0..points.len()).all(|i| {
+            let (x0, y0) = coords(&points[i]);
+            let (x1, y1) = coords(&points[(i + 1) % points.len()]);
+            (x1 - x0) * (y - y0) - (y1 - y0) * (x - x0) >= 0.0
+        }))
+    }
+
+    /// Circumradius over twice the inradius of a triangle: 1 when equilateral, growing without
+    /// bound as the triangle flattens.
+    #[inline]
+    pub fn aspect_ratio(&self) -> Option<S> {
+        let circumradius = self.circumradius()?.to_f64();
+        let (_, inradius) = self.incircle()?;
+        Some(S::from_f64(circumradius / (2.0 * inradius.to_f64())))
+    }
+}
+
+impl<'a, T: Debug + Copy, S: Float> Edge<'a, T, S> {
+    #[inline]
+    fn points(&self) -> Option<((f64, f64), (f64, f64))> {
+        let (origin, destination) = self.vertices();
+        Some((
+            coords(&origin.position().left()?),
+            coords(&destination.position().left()?),
+        ))
+    }
+
+    #[inline]
+    pub fn length(&self) -> Option<S> {
+        let (p0, p1) = self.points()?;
+        Some(S::from_f64(distance(p0, p1)))
+    }
+
+    #[inline]
+    pub fn midpoint(&self) -> Option<Point<S>> {
+        let ((x0, y0), (x1, y1)) = self.points()?;
+        Some(point((x0 + x1) / 2.0, (y0 + y1) / 2.0))
+    }
+
+    /// Unit vector from origin to destination, `None` as well for zero-length edges.
+    #[inline]
+    pub fn direction(&self) -> Option<Vector<S>> {
+        let (p0, p1) = self.points()?;
+        let length = distance(p0, p1);
+        if length > 0.0 {
+            Some(Vector::new(
+                S::from_f64((p1.0 - p0.0) / length),
+                S::from_f64((p1.1 - p0.1) / length),
+            ))
+        } else {
+            None
+        }
+    }
+}
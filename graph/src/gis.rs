@@ -0,0 +1,325 @@
+//! GeoJSON and WKT export of the finite faces of a graph, such as Delaunay triangles or
+//! clipped Voronoi cells, and import of point sites. GeoJSON needs the `geojson` feature.
+//!
+//! Face attributes of common scalar types (`bool`, integers, floats, `String` and ids) are
+//! written as properties; others are left out.
+
+use crate::graph::{Face, Graph};
+use crate::{EdgeId, FaceId, Float, Point, VertexId};
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+use std::io::{self, Read, Write};
+
+#[derive(Clone, Debug)]
+enum Property {
+    Bool(bool),
+    Signed(i64),
+    Unsigned(u64),
+    Float(f64),
+    Text(String),
+}
+
+impl Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Property::Bool(value) => write!(f, "{}", value),
+            Property::Signed(value) => write!(f, "{}", value),
+            Property::Unsigned(value) => write!(f, "{}", value),
+            Property::Float(value) => write!(f, "{}", value),
+            Property::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+// Face attributes with a representable type, sorted by name.
+fn properties<S: Float>(graph: &Graph<(), S>) -> Vec<(&str, Vec<Property>)> {
+    let attributes = graph.face_attributes();
+    let mut names = attributes.names().collect::<Vec<_>>();
+    names.sort_unstable();
+
+    macro_rules! column {
+        ($name:expr, $($type:ty => $variant:expr),* $(,)?) => {
+            $(
+                if let Some(values) = attributes.get::<$type>($name) {
+                    Some(values.iter().map(|value| ($variant)(value.clone())).collect::<Vec<_>>())
+                } else
+            )* {
+                None
+            }
+        };
+    }
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let values = column!(name,
+                bool => Property::Bool,
+                i8 => |value| Property::Signed(i64::from(value)),
+                i16 => |value| Property::Signed(i64::from(value)),
+                i32 => |value| Property::Signed(i64::from(value)),
+                i64 => Property::Signed,
+                isize => |value| Property::Signed(value as i64),
+                u8 => |value| Property::Unsigned(u64::from(value)),
+                u16 => |value| Property::Unsigned(u64::from(value)),
+                u32 => |value| Property::Unsigned(u64::from(value)),
+                u64 => Property::Unsigned,
+                usize => |value| Property::Unsigned(value as u64),
+                f32 => |value| Property::Float(f64::from(value)),
+                f64 => Property::Float,
+                String => Property::Text,
+                VertexId => |value: VertexId| Property::Unsigned(value.index() as u64),
+                EdgeId => |value: EdgeId| Property::Unsigned(value.index() as u64),
+                FaceId => |value: FaceId| Property::Unsigned(value.index() as u64),
+            );
+            values.map(|values| (name, values))
+        })
+        .collect()
+}
+
+// Corners of a finite face, or `None` for faces on the infinite vertex.
+#[inline]
+fn ring<S: Float>(face: &Face<'_, (), S>) -> Option<Vec<(f64, f64)>> {
+    face.vertices()
+        .map(|vertex| {
+            vertex
+                .position()
+                .left()
+                .map(|point| (point.x.to_f64(), point.y.to_f64()))
+        })
+        .collect()
+}
+
+// Text for a delimited-text cell, quoted as in CSV when it holds a tab, line break or quote.
+fn cell(text: &str) -> Cow<'_, str> {
+    if text.contains(['\t', '\n', '\r', '"']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Writes every finite face of `graph` as a `POLYGON` in a tab-separated table with a `wkt`
+/// column and one column per face attribute, as GIS tools load delimited text. Names and text
+/// holding a tab, line break or `"` are put in quotes, with quotes doubled.
+pub fn write_wkt<S: Float, W: Write>(graph: &Graph<(), S>, mut writer: W) -> io::Result<()> {
+    let properties = properties(graph);
+    write!(writer, "wkt")?;
+    for (name, _) in &properties {
+        write!(writer, "\t{}", cell(name))?;
+    }
+    writeln!(writer)?;
+
+    for face in graph.faces() {
+        let ring = match ring(&face) {
+            Some(ring) => ring,
+            None => continue,
+        };
+
+        write!(writer, "POLYGON ((")?;
+        for (i, (x, y)) in ring.iter().chain(ring.first()).enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            write!(writer, "{}{} {}", separator, x, y)?;
+        }
+        write!(writer, "))")?;
+
+        for (_, values) in &properties {
+            match &values[face.id().index()] {
+                Property::Text(text) => write!(writer, "\t{}", cell(text))?,
+                value => write!(writer, "\t{}", value)?,
+            }
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+#[inline]
+fn invalid(message: impl Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Reads the sites of every `POINT` and `MULTIPOINT` in WKT text, in either `MULTIPOINT`
+/// notation. Extra `Z` and `M` ordinates are dropped; any other geometry is an error.
+pub fn read_wkt_points<R: Read, S: Float>(mut reader: R) -> io::Result<Vec<Point<S>>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    let mut points = Vec::new();
+    let mut rest = text.as_str();
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',' || c == ';');
+        if rest.is_empty() {
+            return Ok(points);
+        }
+
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let keyword = rest[..end].to_ascii_uppercase();
+        if keyword != "POINT" && keyword != "MULTIPOINT" {
+            return Err(invalid(format!("unsupported geometry `{}`", &rest[..end])));
+        }
+        rest = rest[end..].trim_start();
+
+        // Dimension tags, or an empty geometry.
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        if rest[..end].eq_ignore_ascii_case("EMPTY") {
+            rest = &rest[end..];
+            continue;
+        }
+        rest = rest[end..].trim_start();
+
+        if !rest.starts_with('(') {
+            return Err(invalid(format!("expected `(` after {}", keyword)));
+        }
+        let mut depth = 0;
+        let close = rest
+            .char_indices()
+            .find(|&(_, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })
+            .map(|(i, _)| i)
+            .ok_or_else(|| invalid(format!("unclosed {}", keyword)))?;
+
+        for coordinates in rest[1..close].split(',') {
+            let coordinates =
+                coordinates.trim_matches(|c: char| c.is_whitespace() || c == '(' || c == ')');
+            if coordinates.eq_ignore_ascii_case("EMPTY") {
+                continue;
+            }
+            let mut values = coordinates.split_whitespace().map(|value| {
+                value
+                    .parse::<f64>()
+                    .map_err(|_| invalid(format!("invalid coordinate `{}`", value)))
+            });
+            match (values.next(), values.next()) {
+                (Some(x), Some(y)) => points.push(Point::new(S::from_f64(x?), S::from_f64(y?))),
+                _ => return Err(invalid(format!("invalid point `{}`", coordinates))),
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+}
+
+#[cfg(feature = "geojson")]
+mod geojson {
+    use super::{invalid, properties, ring, Property};
+    use crate::graph::Graph;
+    use crate::{Float, Point};
+    use serde_json::{json, Map, Value};
+    use std::io::{self, Read, Write};
+
+    impl From<Property> for Value {
+        #[inline]
+        fn from(property: Property) -> Self {
+            match property {
+                Property::Bool(value) => Value::from(value),
+                Property::Signed(value) => Value::from(value),
+                Property::Unsigned(value) => Value::from(value),
+                Property::Float(value) => Value::from(value),
+                Property::Text(value) => Value::from(value),
+            }
+        }
+    }
+
+    /// Writes every finite face of `graph` as a `Polygon` feature of a `FeatureCollection`,
+    /// with the face attributes and the face id under `"face"` as properties.
+    pub fn write_geojson<S: Float, W: Write>(graph: &Graph<(), S>, writer: W) -> io::Result<()> {
+        let properties = properties(graph);
+        let features = graph
+            .faces()
+            .filter_map(|face| {
+                let ring = ring(&face)?;
+                let coordinates = ring
+                    .iter()
+                    .chain(ring.first())
+                    .map(|&(x, y)| json!([x, y]))
+                    .collect::<Vec<_>>();
+
+                let id = face.id().index();
+                let mut map = Map::new();
+                map.insert("face".to_owned(), Value::from(id));
+                for (name, values) in &properties {
+                    map.insert((*name).to_owned(), Value::from(values[id].clone()));
+                }
+
+                Some(json!({
+                    "type": "Feature",
+                    "geometry": { "type": "Polygon", "coordinates": [coordinates] },
+                    "properties": map,
+                }))
+            })
+            .collect::<Vec<_>>();
+
+        let collection = json!({ "type": "FeatureCollection", "features": features });
+        serde_json::to_writer(writer, &collection).map_err(io::Error::from)
+    }
+
+    fn position<S: Float>(value: &Value) -> io::Result<Point<S>> {
+        match value.as_array().map(Vec::as_slice) {
+            Some([x, y, ..]) => match (x.as_f64(), y.as_f64()) {
+                (Some(x), Some(y)) => Ok(Point::new(S::from_f64(x), S::from_f64(y))),
+                _ => Err(invalid(format!("invalid position {}", value))),
+            },
+            _ => Err(invalid(format!("invalid position {}", value))),
+        }
+    }
+
+    fn collect<S: Float>(value: &Value, points: &mut Vec<Point<S>>) -> io::Result<()> {
+        let member = |name: &str| {
+            value
+                .get(name)
+                .ok_or_else(|| invalid(format!("missing member `{}`", name)))
+        };
+        let array = |name: &str| {
+            member(name)?
+                .as_array()
+                .ok_or_else(|| invalid(format!("`{}` is not an array", name)))
+        };
+
+        match member("type")?.as_str() {
+            Some("FeatureCollection") => {
+                for feature in array("features")? {
+                    collect(feature, points)?;
+                }
+            }
+            Some("Feature") => match member("geometry")? {
+                Value::Null => {}
+                geometry => collect(geometry, points)?,
+            },
+            Some("GeometryCollection") => {
+                for geometry in array("geometries")? {
+                    collect(geometry, points)?;
+                }
+            }
+            Some("Point") => points.push(position(member("coordinates")?)?),
+            Some("MultiPoint") => {
+                for coordinates in array("coordinates")? {
+                    points.push(position(coordinates)?);
+                }
+            }
+            Some(other) => return Err(invalid(format!("unsupported geometry `{}`", other))),
+            None => return Err(invalid("`type` is not a string")),
+        }
+        Ok(())
+    }
+
+    /// Reads the sites of every `Point` and `MultiPoint` geometry in a GeoJSON object, in
+    /// document order. Features without geometry are skipped; other geometries are errors.
+    pub fn read_geojson_points<R: Read, S: Float>(reader: R) -> io::Result<Vec<Point<S>>> {
+        let value: Value = serde_json::from_reader(reader).map_err(io::Error::from)?;
+        let mut points = Vec::new();
+        collect(&value, &mut points)?;
+        Ok(points)
+    }
+}
+
+#[cfg(feature = "geojson")]
+pub use self::geojson::{read_geojson_points, write_geojson};
@@ -1,139 +1,385 @@
-use crate::Point;
+use crate::attributes::{Attribute, Attributes};
+use crate::{EdgeId, FaceId, Float, HalfEdgeId, Point, VertexId};
 use either::Either;
 use std::fmt::Debug;
 use std::iter::successors;
 
-#[derive(Debug)]
+#[cfg(feature = "compact-index")]
+pub(crate) type Index = u32;
+
+#[cfg(not(feature = "compact-index"))]
+pub(crate) type Index = usize;
+
+#[cfg(feature = "compact-index")]
+#[inline]
+pub(crate) fn fits_index(count: usize) -> bool {
+    count <= Index::MAX as usize
+}
+
+// Edits check `fits_index` up front; anything that still overflows is a bug, and truncating
+// the index would silently corrupt the graph.
+#[cfg(feature = "compact-index")]
+#[inline]
+fn compact(id: usize) -> Index {
+    assert!(
+        fits_index(id),
+        "index {} does not fit in a compact index",
+        id
+    );
+    id as Index
+}
+
+#[cfg(feature = "compact-index")]
+#[inline]
+pub(crate) fn expand(id: Index) -> usize {
+    id as usize
+}
+
+#[cfg(not(feature = "compact-index"))]
+#[inline]
+pub(crate) fn fits_index(_count: usize) -> bool {
+    true
+}
+
+#[cfg(not(feature = "compact-index"))]
+#[inline]
+fn compact(id: usize) -> Index {
+    id
+}
+
+#[cfg(not(feature = "compact-index"))]
+#[inline]
+pub(crate) fn expand(id: Index) -> usize {
+    id
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphEdge {
-    pub vertex: usize,
-    pub next: usize,
-    pub face: usize,
+    vertex: Index,
+    next: Index,
+    face: Index,
 }
 
 impl GraphEdge {
     #[inline]
     pub fn new(vertex: usize, next: usize, face: usize) -> GraphEdge {
-        GraphEdge { vertex, next, face }
+        GraphEdge {
+            vertex: compact(vertex),
+            next: compact(next),
+            face: compact(face),
+        }
+    }
+
+    #[inline]
+    pub fn vertex(&self) -> usize {
+        expand(self.vertex)
+    }
+
+    #[inline]
+    pub fn next(&self) -> usize {
+        expand(self.next)
+    }
+
+    #[inline]
+    pub fn face(&self) -> usize {
+        expand(self.face)
+    }
+
+    #[inline]
+    pub fn set_vertex(&mut self, vertex: usize) {
+        self.vertex = compact(vertex);
+    }
+
+    #[inline]
+    pub fn set_next(&mut self, next: usize) {
+        self.next = compact(next);
+    }
+
+    #[inline]
+    pub fn set_face(&mut self, face: usize) {
+        self.face = compact(face);
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphFace {
-    pub edge: usize,
+    edge: Index,
 }
 
 impl GraphFace {
     #[inline]
     pub fn new(edge: usize) -> GraphFace {
-        GraphFace { edge }
+        GraphFace {
+            edge: compact(edge),
+        }
+    }
+
+    #[inline]
+    pub fn edge(&self) -> usize {
+        expand(self.edge)
+    }
+
+    #[inline]
+    pub fn set_edge(&mut self, edge: usize) {
+        self.edge = compact(edge);
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphVertex<T: Debug + Copy> {
-    pub edge: usize,
-    pub position: Either<usize, T>,
+    edge: Index,
+    position: Either<Index, T>,
 }
 
 impl<T: Debug + Copy> GraphVertex<T> {
     #[inline]
     pub fn new(edge: usize, position: Either<usize, T>) -> GraphVertex<T> {
-        GraphVertex { edge, position }
+        GraphVertex {
+            edge: compact(edge),
+            position: position.map_left(compact),
+        }
+    }
+
+    #[inline]
+    pub fn edge(&self) -> usize {
+        expand(self.edge)
+    }
+
+    #[inline]
+    pub fn position(&self) -> Either<usize, T> {
+        self.position.map_left(expand)
+    }
+
+    #[inline]
+    pub fn set_edge(&mut self, edge: usize) {
+        self.edge = compact(edge);
+    }
+
+    #[inline]
+    pub fn set_position(&mut self, position: Either<usize, T>) {
+        self.position = position.map_left(compact);
     }
 }
 
 #[derive(Debug, Copy, Clone)]
-pub struct Edge<'a, T: Debug + Copy> {
-    graph: &'a Graph<T>,
+pub struct Edge<'a, T: Debug + Copy, S: Float = f64> {
+    graph: &'a Graph<T, S>,
     id: usize,
 }
 
-impl<'a, T: Debug + Copy> Edge<'a, T> {
+impl<'a, T: Debug + Copy, S: Float> Edge<'a, T, S> {
+    #[inline]
+    pub fn id(&self) -> EdgeId {
+        EdgeId::new(self.id >> 1)
+    }
+
+    #[inline]
+    pub fn half_edge_id(&self) -> HalfEdgeId {
+        HalfEdgeId::new(self.id)
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> (Vertex<'a, T, S>, Vertex<'a, T, S>) {
+        (self.origin(), self.destination())
+    }
+
+    #[inline]
+    pub fn origin(&self) -> Vertex<'a, T, S> {
+        self.graph.vertex_at(self.graph.edges[self.id].vertex())
+    }
+
+    #[inline]
+    pub fn destination(&self) -> Vertex<'a, T, S> {
+        self.graph.vertex_at(self.graph.edges[self.id ^ 1].vertex())
+    }
+
+    #[inline]
+    pub fn twin(&self) -> Edge<'a, T, S> {
+        self.graph.edge_at(self.id ^ 1)
+    }
+
     #[inline]
-    pub fn id(&self) -> usize {
-        self.id / 2
+    pub fn next(&self) -> Edge<'a, T, S> {
+        self.graph.edge_at(self.graph.edges[self.id].next() ^ 1)
+    }
+
+    #[inline]
+    pub fn prev(&self) -> Edge<'a, T, S> {
+        let mut current = *self;
+        loop {
+            let next = current.next();
+            if next.id == self.id {
+                break current;
+            }
+            current = next;
+        }
     }
 
     #[inline]
-    pub fn vertices(&self) -> (Vertex<'a, T>, Vertex<'a, T>) {
-        let id = self.id;
+    pub fn left_face(&self) -> Face<'a, T, S> {
+        self.graph.face_at(self.graph.edges[self.id ^ 1].face())
+    }
 
-        let left = Vertex {
-            graph: &self.graph,
-            id: self.graph.edges[id].vertex,
-        };
+    #[inline]
+    pub fn right_face(&self) -> Face<'a, T, S> {
+        self.graph.face_at(self.graph.edges[self.id].face())
+    }
 
-        let right = Vertex {
-            graph: &self.graph,
-            id: self.graph.edges[id ^ 1].vertex,
-        };
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        self.origin().is_infinite() || self.destination().is_infinite()
+    }
 
-        (left, right)
+    #[inline]
+    pub fn attribute<A: Attribute>(&self, name: &str) -> Option<&'a A> {
+        let graph = self.graph;
+        graph
+            .edge_attributes
+            .get(name)
+            .map(|values| &values[self.id >> 1])
     }
 }
 
 #[derive(Debug, Copy, Clone)]
-pub struct Face<'a, T: Debug + Copy> {
-    graph: &'a Graph<T>,
+pub struct Face<'a, T: Debug + Copy, S: Float = f64> {
+    graph: &'a Graph<T, S>,
     id: usize,
 }
 
-impl<'a, T: Debug + Copy> Face<'a, T> {
+impl<'a, T: Debug + Copy, S: Float> Face<'a, T, S> {
     #[inline]
-    pub fn id(&self) -> usize {
-        self.id
+    pub fn id(&self) -> FaceId {
+        FaceId::new(self.id)
     }
 
     #[inline]
-    pub fn edges(&self) -> impl Iterator<Item = Edge<'_, T>> {
-        let edge = self.graph.faces[self.id].edge;
+    pub fn edges(&self) -> impl Iterator<Item = Edge<'a, T, S>> {
+        let first = self.graph.edge_at(self.graph.faces[self.id].edge());
 
-        successors(Some(self.graph.edge(edge)), move |p| {
-            let n = self.graph.edges[p.id].next ^ 1;
-            if edge == n {
-                None
-            } else {
-                Some(self.graph.edge(n))
-            }
+        successors(Some(first), move |e| {
+            Some(e.next()).filter(|n| n.id != first.id)
         })
     }
 
     #[inline]
-    pub fn vertices(&self) -> impl Iterator<Item = Vertex<'_, T>> {
-        self.edges().map(|edge| edge.vertices().0)
+    pub fn vertices(&self) -> impl Iterator<Item = Vertex<'a, T, S>> {
+        self.edges().map(|edge| edge.origin())
+    }
+
+    #[inline]
+    pub fn neighbors(&self) -> impl Iterator<Item = Face<'a, T, S>> {
+        self.edges().map(|edge| edge.right_face())
+    }
+
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        self.vertices().any(|vertex| vertex.is_infinite())
+    }
+
+    #[inline]
+    pub fn attribute<A: Attribute>(&self, name: &str) -> Option<&'a A> {
+        let graph = self.graph;
+        graph
+            .face_attributes
+            .get(name)
+            .map(|values| &values[self.id])
     }
 }
 
 #[derive(Debug, Copy, Clone)]
-pub struct Vertex<'a, T: Debug + Copy> {
-    graph: &'a Graph<T>,
+pub struct Vertex<'a, T: Debug + Copy, S: Float = f64> {
+    graph: &'a Graph<T, S>,
     id: usize,
 }
 
-impl<'a, T: Debug + Copy> Vertex<'a, T> {
+impl<'a, T: Debug + Copy, S: Float> Vertex<'a, T, S> {
     #[inline]
-    pub fn id(&self) -> usize {
-        self.id
+    pub fn id(&self) -> VertexId {
+        VertexId::new(self.id)
     }
 
     #[inline]
-    pub fn position(&self) -> Either<Point, T> {
+    pub fn position(&self) -> Either<Point<S>, T> {
         self.graph.vertices[self.id]
-            .position
+            .position()
             .map_left(|id| self.graph.points[id])
     }
+
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        self.graph.vertices[self.id].position().is_right()
+    }
+
+    #[inline]
+    pub fn attribute<A: Attribute>(&self, name: &str) -> Option<&'a A> {
+        let graph = self.graph;
+        graph
+            .vertex_attributes
+            .get(name)
+            .map(|values| &values[self.id])
+    }
+
+    /// Edges leaving this vertex, in clockwise order.
+    #[inline]
+    pub fn outgoing_edges(&self) -> impl Iterator<Item = Edge<'a, T, S>> {
+        let first = self.graph.edge_at(self.graph.vertices[self.id].edge() ^ 1);
+
+        successors(Some(first), move |e| {
+            Some(e.twin().next()).filter(|n| n.id != first.id)
+        })
+    }
+
+    #[inline]
+    pub fn incoming_edges(&self) -> impl Iterator<Item = Edge<'a, T, S>> {
+        self.outgoing_edges().map(|edge| edge.twin())
+    }
+
+    #[inline]
+    pub fn neighbors(&self) -> impl Iterator<Item = Vertex<'a, T, S>> {
+        self.outgoing_edges().map(|edge| edge.destination())
+    }
+
+    #[inline]
+    pub fn faces(&self) -> impl Iterator<Item = Face<'a, T, S>> {
+        self.outgoing_edges().map(|edge| edge.left_face())
+    }
 }
 
 #[derive(Debug)]
-pub struct Graph<T: Debug + Copy> {
-    pub(crate) points: Vec<Point>,
+pub struct Graph<T: Debug + Copy, S: Float = f64> {
+    pub(crate) points: Vec<Point<S>>,
 
     pub(crate) edges: Vec<GraphEdge>,
     pub(crate) faces: Vec<GraphFace>,
     pub(crate) vertices: Vec<GraphVertex<T>>,
+
+    pub(crate) edge_attributes: Attributes,
+    pub(crate) face_attributes: Attributes,
+    pub(crate) vertex_attributes: Attributes,
 }
 
-impl<T: Debug + Copy> Graph<T> {
+impl<T: Debug + Copy, S: Float> Graph<T, S> {
+    #[inline]
+    pub(crate) fn new(
+        points: Vec<Point<S>>,
+        edges: Vec<GraphEdge>,
+        faces: Vec<GraphFace>,
+        vertices: Vec<GraphVertex<T>>,
+    ) -> Self {
+        Graph {
+            edge_attributes: Attributes::new(edges.len() / 2),
+            face_attributes: Attributes::new(faces.len()),
+            vertex_attributes: Attributes::new(vertices.len()),
+            points,
+            edges,
+            faces,
+            vertices,
+        }
+    }
+
     #[inline]
     pub fn edge_count(&self) -> usize {
         self.edges.len() / 2
@@ -150,32 +396,90 @@ impl<T: Debug + Copy> Graph<T> {
     }
 
     #[inline]
-    pub fn edge(&self, id: usize) -> Edge<'_, T> {
-        Edge { graph: &self, id }
+    pub fn edge_attributes(&self) -> &Attributes {
+        &self.edge_attributes
+    }
+
+    #[inline]
+    pub fn face_attributes(&self) -> &Attributes {
+        &self.face_attributes
+    }
+
+    #[inline]
+    pub fn vertex_attributes(&self) -> &Attributes {
+        &self.vertex_attributes
+    }
+
+    #[inline]
+    pub fn edge_attributes_mut(&mut self) -> &mut Attributes {
+        &mut self.edge_attributes
+    }
+
+    #[inline]
+    pub fn face_attributes_mut(&mut self) -> &mut Attributes {
+        &mut self.face_attributes
+    }
+
+    #[inline]
+    pub fn vertex_attributes_mut(&mut self) -> &mut Attributes {
+        &mut self.vertex_attributes
+    }
+
+    #[inline]
+    pub fn memory_usage(&self) -> usize {
+        self.points.capacity() * size_of::<Point<S>>()
+            + self.edges.capacity() * size_of::<GraphEdge>()
+            + self.faces.capacity() * size_of::<GraphFace>()
+            + self.vertices.capacity() * size_of::<GraphVertex<T>>()
+    }
+
+    #[inline]
+    pub(crate) fn edge_at(&self, id: usize) -> Edge<'_, T, S> {
+        Edge { graph: self, id }
+    }
+
+    #[inline]
+    pub(crate) fn face_at(&self, id: usize) -> Face<'_, T, S> {
+        Face { graph: self, id }
+    }
+
+    #[inline]
+    pub(crate) fn vertex_at(&self, id: usize) -> Vertex<'_, T, S> {
+        Vertex { graph: self, id }
+    }
+
+    #[inline]
+    pub fn edge(&self, id: EdgeId) -> Edge<'_, T, S> {
+        self.edge_at(id.half_edges().0.index())
+    }
+
+    #[inline]
+    pub fn half_edge(&self, id: HalfEdgeId) -> Edge<'_, T, S> {
+        self.edge_at(id.index())
     }
 
     #[inline]
-    pub fn face(&self, id: usize) -> Face<'_, T> {
-        Face { graph: &self, id }
+    pub fn face(&self, id: FaceId) -> Face<'_, T, S> {
+        self.face_at(id.index())
     }
 
     #[inline]
-    pub fn vertex(&self, id: usize) -> Vertex<'_, T> {
-        Vertex { graph: &self, id }
+    pub fn vertex(&self, id: VertexId) -> Vertex<'_, T, S> {
+        self.vertex_at(id.index())
     }
 
     #[inline]
-    pub fn edges(&self) -> impl Iterator<Item = Edge<'_, T>> {
-        (0..self.edge_count()).map(move |id| self.edge(id << 1))
+    pub fn edges(&self) -> impl Iterator<Item = Edge<'_, T, S>> {
+        (0..self.edge_count()).map(move |id| self.edge_at(id << 1))
     }
 
     #[inline]
-    pub fn faces(&self) -> impl Iterator<Item = Face<'_, T>> {
-        (0..self.face_count()).map(move |id| self.face(id))
+    pub fn faces(&self) -> impl Iterator<Item = Face<'_, T, S>> {
+        (0..self.face_count()).map(move |id| self.face_at(id))
     }
 
     #[inline]
-    pub fn vertices(&self) -> impl Iterator<Item = Vertex<'_, T>> {
-        (0..self.vertex_count()).map(move |id| self.vertex(id))
+    pub fn vertices(&self) -> impl Iterator<Item = Vertex<'_, T, S>> {
+        (0..self.vertex_count()).map(move |id| self.vertex_at(id))
     }
 }
@@ -0,0 +1,128 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+macro_rules! id {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
+        pub struct $name(usize);
+
+        impl $name {
+            #[inline]
+            pub const fn new(index: usize) -> Self {
+                $name(index)
+            }
+
+            #[inline]
+            pub const fn index(self) -> usize {
+                self.0
+            }
+        }
+
+        impl From<$name> for usize {
+            #[inline]
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl<T> Index<$name> for [T] {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, id: $name) -> &T {
+                &self[id.0]
+            }
+        }
+
+        impl<T> IndexMut<$name> for [T] {
+            #[inline]
+            fn index_mut(&mut self, id: $name) -> &mut T {
+                &mut self[id.0]
+            }
+        }
+
+        impl<T> Index<$name> for Vec<T> {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, id: $name) -> &T {
+                &self[id.0]
+            }
+        }
+
+        impl<T> IndexMut<$name> for Vec<T> {
+            #[inline]
+            fn index_mut(&mut self, id: $name) -> &mut T {
+                &mut self[id.0]
+            }
+        }
+    };
+}
+
+id!(
+    /// Index of a vertex. Ids are only made from raw indices with `new`, so one kind of id
+    /// cannot stand in for another or for a plain number:
+    ///
+    /// ```
+    /// let vertex = graph::VertexId::new(3);
+    /// assert_eq!(vertex.index(), 3);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let vertex: graph::VertexId = 3usize.into();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let face: graph::FaceId = graph::VertexId::new(3);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// fn site(vertex: graph::VertexId) {}
+    /// site(graph::FaceId::new(3));
+    /// ```
+    VertexId
+);
+id!(
+    /// Index of an edge, the pair of half-edges `2 * index` and `2 * index + 1`.
+    EdgeId
+);
+id!(
+    /// Index of a half-edge. Its twin differs only in the lowest bit.
+    HalfEdgeId
+);
+id!(
+    /// Index of a face.
+    FaceId
+);
+
+impl EdgeId {
+    #[inline]
+    pub const fn half_edges(self) -> (HalfEdgeId, HalfEdgeId) {
+        (HalfEdgeId(self.0 << 1), HalfEdgeId((self.0 << 1) | 1))
+    }
+}
+
+impl HalfEdgeId {
+    #[inline]
+    pub const fn edge(self) -> EdgeId {
+        EdgeId(self.0 >> 1)
+    }
+
+    #[inline]
+    pub const fn twin(self) -> HalfEdgeId {
+        HalfEdgeId(self.0 ^ 1)
+    }
+}
@@ -1,10 +1,45 @@
+mod attributes;
+mod binary;
+mod build;
+mod cell;
+mod clip;
+pub mod cloud;
 mod delaunator;
 mod delaunay;
+pub mod distributions;
+mod dual;
+mod edit;
+mod geometry;
+pub mod gis;
 mod graph;
+mod id;
+mod lloyd;
+mod mesh;
+mod path;
 mod point;
+mod poisson;
+mod quality;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod triangle;
+mod validate;
 mod voronoi;
 
+pub use crate::attributes::{Attribute, Attributes};
+#[cfg(feature = "mmap")]
+pub use crate::binary::MappedGraph;
+pub use crate::binary::{EdgeView, FaceView, GraphView, VertexView};
+pub use crate::cell::Cell;
+pub use crate::clip::Bounds;
 pub use crate::delaunay::Delaunay;
+pub use crate::edit::{EditError, Renumbering};
 pub use crate::graph::{Edge, Face, Graph, Vertex};
-pub use crate::point::{Point, Vector};
-pub use crate::voronoi::Voronoi;
+pub use crate::id::{EdgeId, FaceId, HalfEdgeId, VertexId};
+pub use crate::lloyd::{lloyd_relax, Lloyd, RelaxError};
+pub use crate::mesh::MeshWriter;
+pub use crate::path::{DistanceField, Path, PathFinder};
+pub use crate::point::{Density, Float, Point, Vector};
+pub use crate::poisson::PoissonDisk;
+pub use crate::quality::{Histogram, QualityReport, Summary};
+pub use crate::validate::ValidationError;
+pub use crate::voronoi::{Ray, Voronoi};
@@ -0,0 +1,173 @@
+use crate::{Bounds, Delaunay, Density, FaceId, Float, Point, ValidationError, VertexId, Voronoi};
+use either::Left;
+use std::fmt::{self, Display};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RelaxError {
+    /// The sites could not be triangulated, for instance because they are all collinear.
+    Triangulation,
+    /// The Voronoi diagram could not be clipped to the bounds.
+    Clip(ValidationError),
+}
+
+impl Display for RelaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelaxError::Triangulation => write!(f, "sites cannot be triangulated"),
+            RelaxError::Clip(error) => write!(f, "cells cannot be clipped: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RelaxError {}
+
+pub struct Lloyd<'a, S: Float = f64> {
+    bounds: Bounds<S>,
+    iterations: usize,
+    tolerance: S,
+    density: Option<Box<Density<'a, S>>>,
+}
+
+impl<'a, S: Float> Lloyd<'a, S> {
+    #[inline]
+    pub fn new(bounds: Bounds<S>, iterations: usize) -> Self {
+        Lloyd {
+            bounds,
+            iterations,
+            tolerance: S::zero(),
+            density: None,
+        }
+    }
+
+    /// Stops once no site moves farther than `tolerance` in an iteration.
+    #[inline]
+    pub fn with_tolerance(mut self, tolerance: S) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Weights centroids by `density`, sampled at edge midpoints of a fan over each cell.
+    #[inline]
+    pub fn with_density(mut self, density: impl Fn(&Point<S>) -> S + 'a) -> Self {
+        self.density = Some(Box::new(density));
+        self
+    }
+
+    /// Moves every site to the centroid of its clipped Voronoi cell, taking all of its pieces
+    /// together, and returns the number of iterations run. Duplicate sites and sites whose cell misses the bounds stay put.
+    pub fn relax(&self, points: &mut [Point<S>]) -> Result<usize, RelaxError> {
+        let mut buffer = Vec::with_capacity(points.len());
+        let tolerance = self.tolerance.to_f64();
+
+        for iteration in 0..self.iterations {
+            buffer.clear();
+            buffer.extend_from_slice(points);
+
+            let delaunay = Delaunay::from(std::mem::take(&mut buffer))
+                .map_err(|_| RelaxError::Triangulation)?;
+            let clipped = Voronoi::from(&delaunay)
+                .clipped(&self.bounds)
+                .map_err(RelaxError::Clip)?;
+            let sites = clipped.face_attributes().get::<VertexId>("site").unwrap();
+
+            // Concave bounds may cut a cell into several pieces.
+            let mut cells = vec![Vec::new(); points.len()];
+            for (face, &site) in sites.iter().enumerate() {
+                let index = match delaunay.vertices[site].position() {
+                    Left(index) => index,
+                    _ => continue,
+                };
+                let piece = clipped
+                    .face(FaceId::new(face))
+                    .vertices()
+                    .map(|vertex| vertex.position().left())
+                    .collect::<Option<Vec<_>>>();
+                if let Some(piece) = piece {
+                    cells[index].push(piece);
+                }
+            }
+
+            let mut moved = 0.0f64;
+            for (index, pieces) in cells.iter().enumerate() {
+                if pieces.is_empty() {
+                    continue;
+                }
+                let centroid = self.centroid(pieces);
+
+                let (dx, dy) = (
+                    centroid.x.to_f64() - points[index].x.to_f64(),
+                    centroid.y.to_f64() - points[index].y.to_f64(),
+                );
+                moved = moved.max(dx.hypot(dy));
+                points[index] = centroid;
+            }
+
+            buffer = delaunay.into_points();
+            if moved <= tolerance {
+                return Ok(iteration + 1);
+            }
+        }
+
+        Ok(self.iterations)
+    }
+
+    fn centroid(&self, pieces: &[Vec<Point<S>>]) -> Point<S> {
+        let (mut x, mut y, mut mass) = (0.0, 0.0, 0.0);
+        for piece in pieces {
+            let (px, py, m) = self.moments(piece);
+            x += px;
+            y += py;
+            mass += m;
+        }
+
+        if mass > 0.0 {
+            Point::new(S::from_f64(x / mass), S::from_f64(y / mass))
+        } else {
+            crate::cell::centroid(&pieces.concat())
+        }
+    }
+
+    // First moments and mass of a piece, weighted by the density if there is one.
+    fn moments(&self, cell: &[Point<S>]) -> (f64, f64, f64) {
+        let density = match &self.density {
+            Some(density) => density,
+            None => {
+                let area = crate::cell::signed_area(cell);
+                let centroid = crate::cell::centroid(cell);
+                return (area * centroid.x.to_f64(), area * centroid.y.to_f64(), area);
+            }
+        };
+
+        let (mut x, mut y, mut mass) = (0.0, 0.0, 0.0);
+        let coords = |p: &Point<S>| (p.x.to_f64(), p.y.to_f64());
+        let (ax, ay) = coords(&cell[0]);
+        for i in 1..cell.len() - 1 {
+            let (bx, by) = coords(&cell[i]);
+            let (cx, cy) = coords(&cell[i + 1]);
+            let area = ((bx - ax) * (cy - ay) - (cx - ax) * (by - ay)) / 2.0;
+
+            for &(mx, my) in &[
+                ((ax + bx) / 2.0, (ay + by) / 2.0),
+                ((bx + cx) / 2.0, (by + cy) / 2.0),
+                ((cx + ax) / 2.0, (cy + ay) / 2.0),
+            ] {
+                let weight =
+                    area / 3.0 * density(&Point::new(S::from_f64(mx), S::from_f64(my))).to_f64();
+                x += weight * mx;
+                y += weight * my;
+                mass += weight;
+            }
+        }
+        (x, y, mass)
+    }
+}
+
+/// Runs `iterations` rounds of Lloyd relaxation of `points` within `bounds`.
+#[inline]
+pub fn lloyd_relax<S: Float>(
+    points: &mut [Point<S>],
+    bounds: &Bounds<S>,
+    iterations: usize,
+) -> Result<usize, RelaxError> {
+    Lloyd::new(bounds.clone(), iterations).relax(points)
+}
@@ -0,0 +1,377 @@
+use crate::graph::Graph;
+use crate::{Attribute, Float, Point};
+use std::convert::TryFrom;
+use std::io::{self, Write};
+
+// Finite part of a graph: vertex positions in output order and faces as indices into them.
+struct Mesh {
+    positions: Vec<(f64, f64, f64)>,
+    faces: Vec<Vec<usize>>,
+    edges: usize,
+    colors: Option<Vec<[u8; 3]>>,
+    properties: Vec<(String, Vec<f64>)>,
+}
+
+fn missing(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("no vertex attribute `{}` of the expected type", name),
+    )
+}
+
+/// Writes the finite faces of a [`Delaunay`](crate::Delaunay) or clipped
+/// [`Voronoi`](crate::Voronoi) graph as OBJ, PLY, OFF or STL. Infinite vertices and the faces
+/// around them are left out, as are faces of zero area in the plane, and the other vertices
+/// are written in id order. With the
+/// infinite vertex 0 as the only one, vertex `v` is vertex `v` of an OBJ file, which counts
+/// from 1, and vertex `v - 1` of a PLY or OFF file, which count from 0. STL repeats the
+/// corners of every triangle instead.
+///
+/// Vertices lie in the plane `z = 0` unless a vertex attribute is picked as height.
+pub struct MeshWriter<'a, S: Float = f64> {
+    graph: &'a Graph<(), S>,
+    z: Option<&'a str>,
+    color: Option<&'a str>,
+    properties: Vec<&'a str>,
+}
+
+impl<'a, S: Float + Attribute> MeshWriter<'a, S> {
+    #[inline]
+    pub fn new(graph: &'a Graph<(), S>) -> Self {
+        MeshWriter {
+            graph,
+            z: None,
+            color: None,
+            properties: Vec::new(),
+        }
+    }
+
+    /// Takes the height of each vertex from the `S` vertex attribute `name`.
+    #[inline]
+    pub fn with_z(mut self, name: &'a str) -> Self {
+        self.z = Some(name);
+        self
+    }
+
+    /// Colours vertices by the `[u8; 3]` vertex attribute `name`. OBJ, OFF and PLY only.
+    #[inline]
+    pub fn with_color(mut self, name: &'a str) -> Self {
+        self.color = Some(name);
+        self
+    }
+
+    /// Adds the `S` vertex attribute `name` as an extra vertex property. PLY only.
+    #[inline]
+    pub fn with_property(mut self, name: &'a str) -> Self {
+        self.properties.push(name);
+        self
+    }
+
+    fn mesh(&self) -> io::Result<Mesh> {
+        let graph = self.graph;
+        let attributes = graph.vertex_attributes();
+        let z = match self.z {
+            Some(name) => Some(attributes.get::<S>(name).ok_or_else(|| missing(name))?),
+            None => None,
+        };
+
+        let mut index = vec![usize::MAX; graph.vertex_count()];
+        let mut vertices = Vec::with_capacity(graph.vertex_count());
+        let mut positions = Vec::with_capacity(graph.vertex_count());
+        for vertex in graph.vertices() {
+            if let Some(point) = vertex.position().left() {
+                let id = vertex.id().index();
+                index[id] = vertices.len();
+                vertices.push(id);
+                positions.push((
+                    point.x.to_f64(),
+                    point.y.to_f64(),
+                    z.map_or(0.0, |z| z[id].to_f64()),
+                ));
+            }
+        }
+
+        // A face without a single left turn has no area, as on the flat triangles the hull of a
+        // Delaunay keeps over collinear sites.
+        let faces = graph
+            .faces()
+            .filter(|face| !face.is_infinite())
+            .filter(|face| {
+                let corners = face
+                    .vertices()
+                    .filter_map(|vertex| vertex.position().left())
+                    .collect::<Vec<_>>();
+                let len = corners.len();
+                (0..len).any(|i| {
+                    Point::is_ccw(
+                        &corners[i],
+                        &corners[(i + 1) % len],
+                        &corners[(i + 2) % len],
+                    )
+                })
+            })
+            .map(|face| {
+                face.vertices()
+                    .map(|vertex| index[vertex.id().index()])
+                    .collect()
+            })
+            .collect();
+
+        let edges = graph.edges().filter(|edge| !edge.is_infinite()).count();
+
+        let colors = match self.color {
+            Some(name) => {
+                let colors = attributes
+                    .get::<[u8; 3]>(name)
+                    .ok_or_else(|| missing(name))?;
+                Some(vertices.iter().map(|&id| colors[id]).collect())
+            }
+            None => None,
+        };
+
+        let properties = self
+            .properties
+            .iter()
+            .map(|&name| {
+                let values = attributes.get::<S>(name).ok_or_else(|| missing(name))?;
+                Ok((
+                    name.to_owned(),
+                    vertices.iter().map(|&id| values[id].to_f64()).collect(),
+                ))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Mesh {
+            positions,
+            faces,
+            edges,
+            colors,
+            properties,
+        })
+    }
+
+    /// Wavefront OBJ, with colours as the common `v x y z r g b` extension.
+    pub fn write_obj<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mesh = self.mesh()?;
+        for (i, &(x, y, z)) in mesh.positions.iter().enumerate() {
+            write!(writer, "v {} {} {}", x, y, z)?;
+            if let Some(colors) = &mesh.colors {
+                let [r, g, b] = colors[i];
+                write!(
+                    writer,
+                    " {} {} {}",
+                    f64::from(r) / 255.0,
+                    f64::from(g) / 255.0,
+                    f64::from(b) / 255.0
+                )?;
+            }
+            writeln!(writer)?;
+        }
+
+        for face in &mesh.faces {
+            write!(writer, "f")?;
+            for &vertex in face {
+                write!(writer, " {}", vertex + 1)?;
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Object File Format, as `COFF` when colours are written.
+    pub fn write_off<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mesh = self.mesh()?;
+        let header = if mesh.colors.is_some() { "COFF" } else { "OFF" };
+        writeln!(writer, "{}", header)?;
+        writeln!(
+            writer,
+            "{} {} {}",
+            mesh.positions.len(),
+            mesh.faces.len(),
+            mesh.edges
+        )?;
+
+        for (i, &(x, y, z)) in mesh.positions.iter().enumerate() {
+            write!(writer, "{} {} {}", x, y, z)?;
+            if let Some(colors) = &mesh.colors {
+                let [r, g, b] = colors[i];
+                write!(writer, " {} {} {} 255", r, g, b)?;
+            }
+            writeln!(writer)?;
+        }
+
+        for face in &mesh.faces {
+            write!(writer, "{}", face.len())?;
+            for &vertex in face {
+                write!(writer, " {}", vertex)?;
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    /// ASCII PLY. Extra properties are written as `double`.
+    pub fn write_ply<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.ply(writer, false)
+    }
+
+    /// Little-endian binary PLY.
+    pub fn write_ply_binary<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.ply(writer, true)
+    }
+
+    fn ply<W: Write>(&self, mut writer: W, binary: bool) -> io::Result<()> {
+        let mesh = self.mesh()?;
+        if mesh
+            .faces
+            .iter()
+            .any(|face| face.len() > usize::from(u8::MAX))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "face with more than 255 vertices",
+            ));
+        }
+
+        let format = if binary {
+            "binary_little_endian"
+        } else {
+            "ascii"
+        };
+        writeln!(writer, "ply\nformat {} 1.0", format)?;
+        writeln!(writer, "element vertex {}", mesh.positions.len())?;
+        writeln!(
+            writer,
+            "property double x\nproperty double y\nproperty double z"
+        )?;
+        if mesh.colors.is_some() {
+            writeln!(
+                writer,
+                "property uchar red\nproperty uchar green\nproperty uchar blue"
+            )?;
+        }
+        for (name, _) in &mesh.properties {
+            writeln!(writer, "property double {}", name)?;
+        }
+        writeln!(writer, "element face {}", mesh.faces.len())?;
+        writeln!(writer, "property list uchar int vertex_indices\nend_header")?;
+
+        for (i, &(x, y, z)) in mesh.positions.iter().enumerate() {
+            let color = mesh.colors.as_ref().map(|colors| colors[i]);
+            let values = mesh.properties.iter().map(|(_, values)| values[i]);
+            if binary {
+                for value in [x, y, z].iter() {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+                if let Some(color) = color {
+                    writer.write_all(&color)?;
+                }
+                for value in values {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+            } else {
+                write!(writer, "{} {} {}", x, y, z)?;
+                if let Some([r, g, b]) = color {
+                    write!(writer, " {} {} {}", r, g, b)?;
+                }
+                for value in values {
+                    write!(writer, " {}", value)?;
+                }
+                writeln!(writer)?;
+            }
+        }
+
+        for face in &mesh.faces {
+            if binary {
+                writer.write_all(&[face.len() as u8])?;
+                for &vertex in face {
+                    let vertex = i32::try_from(vertex).map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "vertex index does not fit in a PLY int",
+                        )
+                    })?;
+                    writer.write_all(&vertex.to_le_bytes())?;
+                }
+            } else {
+                write!(writer, "{}", face.len())?;
+                for &vertex in face {
+                    write!(writer, " {}", vertex)?;
+                }
+                writeln!(writer)?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// ASCII STL. Polygons are split into fans of triangles.
+    pub fn write_stl<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mesh = self.mesh()?;
+        writeln!(writer, "solid graph")?;
+        for triangle in triangles(&mesh) {
+            let (x, y, z) = normal(&triangle);
+            writeln!(writer, "facet normal {} {} {}", x, y, z)?;
+            writeln!(writer, "outer loop")?;
+            for &(x, y, z) in &triangle {
+                writeln!(writer, "vertex {} {} {}", x as f32, y as f32, z as f32)?;
+            }
+            writeln!(writer, "endloop\nendfacet")?;
+        }
+        writeln!(writer, "endsolid graph")?;
+        writer.flush()
+    }
+
+    /// Binary STL.
+    pub fn write_stl_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mesh = self.mesh()?;
+        let triangles = triangles(&mesh).collect::<Vec<_>>();
+        let count = u32::try_from(triangles.len()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "more than 4294967295 triangles")
+        })?;
+        writer.write_all(&[0; 80])?;
+        writer.write_all(&count.to_le_bytes())?;
+        for triangle in &triangles {
+            let (x, y, z) = normal(triangle);
+            for value in [x, y, z].iter() {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            for &(x, y, z) in triangle {
+                for value in [x, y, z].iter() {
+                    writer.write_all(&(*value as f32).to_le_bytes())?;
+                }
+            }
+            writer.write_all(&[0; 2])?;
+        }
+        writer.flush()
+    }
+}
+
+type Triangle = [(f64, f64, f64); 3];
+
+fn triangles(mesh: &Mesh) -> impl Iterator<Item = Triangle> + '_ {
+    mesh.faces.iter().flat_map(move |face| {
+        (1..face.len().saturating_sub(1)).map(move |i| {
+            [
+                mesh.positions[face[0]],
+                mesh.positions[face[i]],
+                mesh.positions[face[i + 1]],
+            ]
+        })
+    })
+}
+
+fn normal(&[a, b, c]: &Triangle) -> (f32, f32, f32) {
+    let (ux, uy, uz) = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+    let (vx, vy, vz) = (c.0 - a.0, c.1 - a.1, c.2 - a.2);
+    let (x, y, z) = (uy * vz - uz * vy, uz * vx - ux * vz, ux * vy - uy * vx);
+    let length = (x * x + y * y + z * z).sqrt();
+    if length > 0.0 {
+        (
+            (x / length) as f32,
+            (y / length) as f32,
+            (z / length) as f32,
+        )
+    } else {
+        (0.0, 0.0, 1.0)
+    }
+}
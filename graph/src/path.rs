@@ -0,0 +1,285 @@
+use crate::graph::{Edge, Graph, Vertex};
+use crate::{Float, VertexId};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+type Weight<'a, S> = dyn Fn(Edge<'a, (), S>) -> S + 'a;
+type Heuristic<'a, S> = dyn Fn(Vertex<'a, (), S>, Vertex<'a, (), S>) -> S + 'a;
+
+const UNREACHED: usize = usize::MAX;
+
+/// Vertices from source to target, both included, and the summed weight of the edges between.
+#[derive(Clone, Debug)]
+pub struct Path<'a, S: Float = f64> {
+    pub vertices: Vec<Vertex<'a, (), S>>,
+    pub cost: S,
+}
+
+/// Shortest distances from one source to every vertex, with the tree of shortest paths.
+#[derive(Clone, Debug)]
+pub struct DistanceField<'a, S: Float = f64> {
+    graph: &'a Graph<(), S>,
+    source: usize,
+    distances: Vec<f64>,
+    previous: Vec<usize>,
+}
+
+impl<'a, S: Float> DistanceField<'a, S> {
+    #[inline]
+    pub fn source(&self) -> Vertex<'a, (), S> {
+        self.graph.vertex_at(self.source)
+    }
+
+    /// Distance to `vertex`, `None` if it cannot be reached.
+    #[inline]
+    pub fn distance(&self, vertex: VertexId) -> Option<S> {
+        let distance = self.distances[vertex.index()];
+        if distance.is_finite() {
+            Some(S::from_f64(distance))
+        } else {
+            None
+        }
+    }
+
+    /// Distance to every vertex by index, `None` for unreachable ones and the infinite vertex.
+    #[inline]
+    pub fn distances(&self) -> Vec<Option<S>> {
+        (0..self.distances.len())
+            .map(|vertex| self.distance(VertexId::new(vertex)))
+            .collect()
+    }
+
+    /// Shortest path from the source to `vertex`.
+    #[inline]
+    pub fn path_to(&self, vertex: VertexId) -> Option<Path<'a, S>> {
+        path(self.graph, &self.distances, &self.previous, vertex.index())
+    }
+}
+
+// Unvisited vertex with the cost it was reached at and its estimated total.
+#[derive(Copy, Clone, Debug)]
+struct Entry {
+    estimate: f64,
+    cost: f64,
+    vertex: usize,
+}
+
+impl PartialEq for Entry {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    // Reversed, so that the binary heap pops the lowest estimate first.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+fn path<'a, S: Float>(
+    graph: &'a Graph<(), S>,
+    distances: &[f64],
+    previous: &[usize],
+    target: usize,
+) -> Option<Path<'a, S>> {
+    let cost = distances[target];
+    if !cost.is_finite() {
+        return None;
+    }
+
+    let mut vertices = vec![graph.vertex_at(target)];
+    let mut current = target;
+    while previous[current] != UNREACHED {
+        current = previous[current];
+        vertices.push(graph.vertex_at(current));
+    }
+    vertices.reverse();
+
+    Some(Path {
+        vertices,
+        cost: S::from_f64(cost),
+    })
+}
+
+/// Shortest paths along the finite edges of a triangulation. Edges weigh their Euclidean
+/// length unless given a weight function, and blocked vertices are never entered or left.
+pub struct PathFinder<'a, S: Float = f64> {
+    graph: &'a Graph<(), S>,
+    weight: Option<Box<Weight<'a, S>>>,
+    heuristic: Option<Box<Heuristic<'a, S>>>,
+    blocked: Vec<bool>,
+}
+
+impl<'a, S: Float> PathFinder<'a, S> {
+    #[inline]
+    pub fn new(graph: &'a Graph<(), S>) -> Self {
+        PathFinder {
+            graph,
+            weight: None,
+            heuristic: None,
+            blocked: vec![false; graph.vertex_count()],
+        }
+    }
+
+    /// Weighs each edge, taken from origin to destination, by `weight`. Weights must not be
+    /// negative; infinite and NaN weights make an edge impassable.
+    #[inline]
+    pub fn with_weight(mut self, weight: impl Fn(Edge<'a, (), S>) -> S + 'a) -> Self {
+        self.weight = Some(Box::new(weight));
+        self
+    }
+
+    /// Estimates the remaining cost from a vertex to the target in A*. Paths are shortest
+    /// only if it never overestimates. Defaults to the straight-line distance with Euclidean
+    /// weights, and to zero, which makes A* a Dijkstra search, with custom ones.
+    #[inline]
+    pub fn with_heuristic(
+        mut self,
+        heuristic: impl Fn(Vertex<'a, (), S>, Vertex<'a, (), S>) -> S + 'a,
+    ) -> Self {
+        self.heuristic = Some(Box::new(heuristic));
+        self
+    }
+
+    #[inline]
+    pub fn with_blocked(mut self, vertices: impl IntoIterator<Item = VertexId>) -> Self {
+        for vertex in vertices {
+            self.blocked[vertex.index()] = true;
+        }
+        self
+    }
+
+    #[inline]
+    fn weight(&self, edge: Edge<'a, (), S>) -> f64 {
+        match &self.weight {
+            Some(weight) => weight(edge).to_f64(),
+            None => edge.length().map_or(f64::INFINITY, S::to_f64),
+        }
+    }
+
+    #[inline]
+    fn estimate(&self, vertex: usize, target: Option<usize>) -> f64 {
+        let target = match target {
+            Some(target) => self.graph.vertex_at(target),
+            None => return 0.0,
+        };
+        let vertex = self.graph.vertex_at(vertex);
+        match &self.heuristic {
+            Some(heuristic) => heuristic(vertex, target).to_f64(),
+            None if self.weight.is_none() => {
+                match (vertex.position().left(), target.position().left()) {
+                    (Some(p0), Some(p1)) => {
+                        (p1.x.to_f64() - p0.x.to_f64()).hypot(p1.y.to_f64() - p0.y.to_f64())
+                    }
+                    _ => 0.0,
+                }
+            }
+            None => 0.0,
+        }
+    }
+
+    // Best-first search from `source`, stopping once `target` is settled if there is one.
+    // The estimate is zero without a target or unless `guided`, which gives Dijkstra.
+    fn search(&self, source: usize, target: Option<usize>, guided: bool) -> (Vec<f64>, Vec<usize>) {
+        let len = self.graph.vertex_count();
+        let mut distances = vec![f64::INFINITY; len];
+        let mut previous = vec![UNREACHED; len];
+        if self.blocked[source] || self.graph.vertex_at(source).is_infinite() {
+            return (distances, previous);
+        }
+        let goal = target.filter(|_| guided);
+
+        let mut queue = BinaryHeap::new();
+        distances[source] = 0.0;
+        queue.push(Entry {
+            estimate: self.estimate(source, goal),
+            cost: 0.0,
+            vertex: source,
+        });
+
+        while let Some(Entry { cost, vertex, .. }) = queue.pop() {
+            if cost > distances[vertex] {
+                continue;
+            }
+            if Some(vertex) == target {
+                break;
+            }
+
+            for edge in self.graph.vertex_at(vertex).outgoing_edges() {
+                let next = edge.destination();
+                let id = next.id().index();
+                if next.is_infinite() || self.blocked[id] {
+                    continue;
+                }
+
+                let weight = self.weight(edge);
+                debug_assert!(
+                    weight >= 0.0 || weight.is_nan(),
+                    "negative weight {}",
+                    weight
+                );
+                let cost = cost + weight;
+                if cost < distances[id] {
+                    distances[id] = cost;
+                    previous[id] = vertex;
+                    queue.push(Entry {
+                        estimate: cost + self.estimate(id, goal),
+                        cost,
+                        vertex: id,
+                    });
+                }
+            }
+        }
+
+        (distances, previous)
+    }
+
+    #[inline]
+    fn find(&self, from: VertexId, to: VertexId, guided: bool) -> Option<Path<'a, S>> {
+        let target = to.index();
+        if self.blocked[target] {
+            return None;
+        }
+        let (distances, previous) = self.search(from.index(), Some(target), guided);
+        path(self.graph, &distances, &previous, target)
+    }
+
+    /// Shortest path from `from` to `to` by Dijkstra's algorithm.
+    #[inline]
+    pub fn dijkstra(&self, from: VertexId, to: VertexId) -> Option<Path<'a, S>> {
+        self.find(from, to, false)
+    }
+
+    /// Shortest path from `from` to `to` by A*, guided by the heuristic.
+    #[inline]
+    pub fn a_star(&self, from: VertexId, to: VertexId) -> Option<Path<'a, S>> {
+        self.find(from, to, true)
+    }
+
+    /// Distances from `from` to every vertex by Dijkstra's algorithm.
+    #[inline]
+    pub fn distances(&self, from: VertexId) -> DistanceField<'a, S> {
+        let (distances, previous) = self.search(from.index(), None, false);
+        DistanceField {
+            graph: self.graph,
+            source: from.index(),
+            distances,
+            previous,
+        }
+    }
+}
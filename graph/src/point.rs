@@ -1,11 +1,47 @@
+use nalgebra::RealField;
 use rand::distributions::{Distribution, Standard};
 use std::ops::Deref;
 
+pub trait Float: RealField + Copy {
+    const EPSILON: Self;
+
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+}
+
+impl Float for f32 {
+    const EPSILON: Self = f32::EPSILON;
+
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Float for f64 {
+    const EPSILON: Self = f64::EPSILON;
+
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
-pub struct Vector(nalgebra::Vector2<f64>);
+pub struct Vector<S: Float = f64>(nalgebra::Vector2<S>);
 
-impl Deref for Vector {
-    type Target = nalgebra::Vector2<f64>;
+impl<S: Float> Deref for Vector<S> {
+    type Target = nalgebra::Vector2<S>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -13,18 +49,18 @@ impl Deref for Vector {
     }
 }
 
-impl Vector {
+impl<S: Float> Vector<S> {
     #[inline]
-    pub fn new(x: f64, y: f64) -> Self {
+    pub fn new(x: S, y: S) -> Self {
         Vector(nalgebra::Vector2::new(x, y))
     }
 }
 
 #[derive(Clone, Copy, Debug)]
-pub struct Point(nalgebra::Point2<f64>);
+pub struct Point<S: Float = f64>(nalgebra::Point2<S>);
 
-impl Deref for Point {
-    type Target = nalgebra::Point2<f64>;
+impl<S: Float> Deref for Point<S> {
+    type Target = nalgebra::Point2<S>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -32,30 +68,49 @@ impl Deref for Point {
     }
 }
 
-impl Point {
+// Predicates and circumcircles are evaluated in f64 whatever the storage type, with the
+// same double-double fallback, so an f32 mesh gets exactly the decisions of an f64 mesh
+// over the same coordinates. Neither is exact arithmetic: f32 differences and their
+// products usually fit in f64, but not when the coordinates differ widely in magnitude.
+impl<S: Float> Point<S> {
     #[inline]
-    pub fn new(x: f64, y: f64) -> Self {
+    pub fn new(x: S, y: S) -> Self {
         Point(nalgebra::Point2::new(x, y))
     }
 
     #[inline]
-    pub fn is_ccw(p0: &Point, p1: &Point, p2: &Point) -> bool {
-        (p1.y - p0.y) * (p2.x - p1.x) - (p1.x - p0.x) * (p2.y - p1.y) < 0.0
+    fn coords(&self) -> (f64, f64) {
+        (self.x.to_f64(), self.y.to_f64())
+    }
+
+    /// Near-collinear cases that f64 cannot settle are decided again in double-double
+    /// precision.
+    #[inline]
+    pub fn is_ccw(p0: &Self, p1: &Self, p2: &Self) -> bool {
+        let (x0, y0) = p0.coords();
+        let (x1, y1) = p1.coords();
+        let (x2, y2) = p2.coords();
+
+        let (l, r) = ((y1 - y0) * (x2 - x1), (x1 - x0) * (y2 - y1));
+        if (l - r).abs() > 3.4e-16 * (l.abs() + r.abs()) {
+            return l - r < 0.0;
+        }
+
+        let l = dd::mul(dd::diff(y1, y0), dd::diff(x2, x1));
+        let r = dd::mul(dd::diff(x1, x0), dd::diff(y2, y1));
+        dd::sub(l, r).0 < 0.0
     }
 
     #[inline]
-    pub fn nearly_equals(p1: &Point, p2: &Point) -> bool {
-        (p1.x - p2.x).abs() <= std::f64::EPSILON && (p1.y - p2.y).abs() <= std::f64::EPSILON
+    pub fn nearly_equals(p1: &Self, p2: &Self) -> bool {
+        (p1.x - p2.x).abs() <= S::EPSILON && (p1.y - p2.y).abs() <= S::EPSILON
     }
 
     #[inline]
-    fn circumdelta(p0: &Point, p1: &Point, p2: &Point) -> (f64, f64) {
-        let ax = p0.x;
-        let ay = p0.y;
-        let bx = p1.x;
-        let by = p1.y;
-        let cx = p2.x;
-        let cy = p2.y;
+    fn circumdelta(p0: &Self, p1: &Self, p2: &Self) -> (f64, f64) {
+        let (ax, ay) = p0.coords();
+        let (bx, by) = p1.coords();
+        let (cx, cy) = p2.coords();
 
         let dx = bx - ax;
         let dy = by - ay;
@@ -70,40 +125,176 @@ impl Point {
     }
 
     #[inline]
-    pub fn circumradius(p0: &Point, p1: &Point, p2: &Point) -> f64 {
-        let (x, y) = Point::circumdelta(p0, p1, p2);
-        x * x + y * y
+    pub fn circumradius(p0: &Self, p1: &Self, p2: &Self) -> S {
+        let (x, y) = Self::circumdelta(p0, p1, p2);
+        S::from_f64(x * x + y * y)
     }
 
     #[inline]
-    pub fn circumcenter(p0: &Point, p1: &Point, p2: &Point) -> Point {
-        let (x, y) = Point::circumdelta(p0, p1, p2);
-        Point::new(p0.x + x, p0.y + y)
+    pub fn circumcenter(p0: &Self, p1: &Self, p2: &Self) -> Self {
+        let (x, y) = Self::circumdelta(p0, p1, p2);
+        let (ax, ay) = p0.coords();
+        Point::new(S::from_f64(ax + x), S::from_f64(ay + y))
     }
 
+    /// Circumcenter evaluated in double-double precision when the triangle is too flat for
+    /// plain f64, so near-degenerate triangles get distant but accurate centres. Returns
+    /// `None` only for exactly collinear points.
+    pub fn robust_circumcenter(p0: &Self, p1: &Self, p2: &Self) -> Option<Self> {
+        let (ax, ay) = p0.coords();
+        let (bx, by) = p1.coords();
+        let (cx, cy) = p2.coords();
+
+        let (dx, dy, ex, ey) = (bx - ax, by - ay, cx - ax, cy - ay);
+        let (l, r) = (dx * ey, dy * ex);
+        if (l - r).abs() > 1e-12 * (l.abs() + r.abs()) {
+            let center = Self::circumcenter(p0, p1, p2);
+            if center.x.to_f64().is_finite() && center.y.to_f64().is_finite() {
+                return Some(center);
+            }
+        }
+
+        let (dx, dy) = (dd::diff(bx, ax), dd::diff(by, ay));
+        let (ex, ey) = (dd::diff(cx, ax), dd::diff(cy, ay));
+        let det = dd::sub(dd::mul(dx, ey), dd::mul(dy, ex));
+        if det.0 == 0.0 {
+            return None;
+        }
+
+        let bl = dd::add(dd::mul(dx, dx), dd::mul(dy, dy));
+        let cl = dd::add(dd::mul(ex, ex), dd::mul(ey, ey));
+        let det = dd::add(det, det);
+        let x = dd::div(dd::sub(dd::mul(ey, bl), dd::mul(dy, cl)), det);
+        let y = dd::div(dd::sub(dd::mul(dx, cl), dd::mul(ex, bl)), det);
+
+        Some(Point::new(
+            S::from_f64(dd::add(x, (ax, 0.0)).0),
+            S::from_f64(dd::add(y, (ay, 0.0)).0),
+        ))
+    }
+
+    /// Whether `p` lies inside the circle through `a`, `b` and `c`. Near-cocircular cases that
+    /// f64 cannot settle are decided again in double-double precision.
     #[inline]
-    pub fn in_circle(a: &Point, b: &Point, c: &Point, p: &Point) -> bool {
-        let dx = a.x - p.x;
-        let dy = a.y - p.y;
-        let ex = b.x - p.x;
-        let ey = b.y - p.y;
-        let fx = c.x - p.x;
-        let fy = c.y - p.y;
+    pub fn in_circle(a: &Self, b: &Self, c: &Self, p: &Self) -> bool {
+        let (det, permanent) = Self::in_circle_estimate(a, b, c, p);
+        if det.abs() > 1.2e-15 * permanent {
+            return det < 0.0;
+        }
+
+        let (ax, ay) = a.coords();
+        let (bx, by) = b.coords();
+        let (cx, cy) = c.coords();
+        let (px, py) = p.coords();
+        let (dx, dy) = (dd::diff(ax, px), dd::diff(ay, py));
+        let (ex, ey) = (dd::diff(bx, px), dd::diff(by, py));
+        let (fx, fy) = (dd::diff(cx, px), dd::diff(cy, py));
+        let lift = |x, y| dd::add(dd::mul(x, x), dd::mul(y, y));
+        let cross = |x0, y0, x1, y1| dd::sub(dd::mul(x0, y1), dd::mul(y0, x1));
+
+        let det = dd::add(
+            dd::add(
+                dd::mul(lift(dx, dy), cross(ex, ey, fx, fy)),
+                dd::mul(lift(ex, ey), cross(fx, fy, dx, dy)),
+            ),
+            dd::mul(lift(fx, fy), cross(dx, dy, ex, ey)),
+        );
+        det.0 < 0.0
+    }
+
+    /// Like `in_circle`, but `false` when `p` lies on the circle to within rounding, where
+    /// either diagonal of the quad is a valid Delaunay edge.
+    #[inline]
+    pub(crate) fn strictly_in_circle(a: &Self, b: &Self, c: &Self, p: &Self) -> bool {
+        let (det, permanent) = Self::in_circle_estimate(a, b, c, p);
+        det < -1.2e-15 * permanent
+    }
+
+    // The in-circle determinant in f64 along with the sum of its terms' magnitudes, which
+    // bounds its rounding error.
+    #[inline]
+    fn in_circle_estimate(a: &Self, b: &Self, c: &Self, p: &Self) -> (f64, f64) {
+        let (ax, ay) = a.coords();
+        let (bx, by) = b.coords();
+        let (cx, cy) = c.coords();
+        let (px, py) = p.coords();
+
+        let dx = ax - px;
+        let dy = ay - py;
+        let ex = bx - px;
+        let ey = by - py;
+        let fx = cx - px;
+        let fy = cy - py;
 
         let ap = dx * dx + dy * dy;
         let bp = ex * ex + ey * ey;
         let cp = fx * fx + fy * fy;
 
-        dx * (ey * cp - bp * fy) - dy * (ex * cp - bp * fx) + ap * (ex * fy - ey * fx) < 0.0
+        let det = dx * (ey * cp - bp * fy) - dy * (ex * cp - bp * fx) + ap * (ex * fy - ey * fx);
+        let permanent = ap * ((ex * fy).abs() + (ey * fx).abs())
+            + bp * ((dx * fy).abs() + (dy * fx).abs())
+            + cp * ((dx * ey).abs() + (dy * ex).abs());
+        (det, permanent)
     }
 }
 
-impl Distribution<Point> for Standard
+// Double-double arithmetic: unevaluated sums `hi + lo` carrying about 106 bits.
+mod dd {
+    pub type Double = (f64, f64);
+
+    #[inline]
+    fn quick_two_sum(a: f64, b: f64) -> Double {
+        let s = a + b;
+        (s, b - (s - a))
+    }
+
+    #[inline]
+    fn two_sum(a: f64, b: f64) -> Double {
+        let s = a + b;
+        let v = s - a;
+        (s, (a - (s - v)) + (b - v))
+    }
+
+    #[inline]
+    pub fn diff(a: f64, b: f64) -> Double {
+        two_sum(a, -b)
+    }
+
+    #[inline]
+    pub fn add(a: Double, b: Double) -> Double {
+        let (s, e) = two_sum(a.0, b.0);
+        quick_two_sum(s, e + a.1 + b.1)
+    }
+
+    #[inline]
+    pub fn sub(a: Double, b: Double) -> Double {
+        add(a, (-b.0, -b.1))
+    }
+
+    #[inline]
+    pub fn mul(a: Double, b: Double) -> Double {
+        let p = a.0 * b.0;
+        let e = a.0.mul_add(b.0, -p);
+        quick_two_sum(p, e + a.0 * b.1 + a.1 * b.0)
+    }
+
+    #[inline]
+    pub fn div(a: Double, b: Double) -> Double {
+        let q = a.0 / b.0;
+        let r = sub(a, mul((q, 0.0), b));
+        quick_two_sum(q, r.0 / b.0)
+    }
+}
+
+/// Positive weight over the plane, used for weighted centroids and variable spacing.
+pub type Density<'a, S = f64> = dyn Fn(&Point<S>) -> S + 'a;
+
+impl<S: Float> Distribution<Point<S>> for Standard
 where
-    Standard: Distribution<f64>,
+    Standard: Distribution<S>,
 {
     #[inline]
-    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Point {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Point<S> {
         Point::new(rng.gen(), rng.gen())
     }
 }
@@ -0,0 +1,156 @@
+use crate::{Bounds, Density, Float, Point};
+use rand::Rng;
+
+/// Bridson's Poisson-disk sampler.
+pub struct PoissonDisk<'a, S: Float = f64> {
+    min_distance: S,
+    bounds: Bounds<S>,
+    attempts: usize,
+    density: Option<Box<Density<'a, S>>>,
+}
+
+impl<'a, S: Float> PoissonDisk<'a, S> {
+    /// Samples the unit square with no two points closer than `min_distance`.
+    #[inline]
+    pub fn new(min_distance: S) -> Self {
+        PoissonDisk {
+            min_distance,
+            bounds: Bounds::unit(),
+            attempts: 30,
+            density: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_bounds(mut self, bounds: Bounds<S>) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// Candidates tried around each active point before it is retired.
+    #[inline]
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Spaces points `min_distance / sqrt(density)` apart. Density is clamped to 1 so that
+    /// `min_distance` stays a true minimum, and points are never placed where it is not positive.
+    #[inline]
+    pub fn with_density(mut self, density: impl Fn(&Point<S>) -> S + 'a) -> Self {
+        self.density = Some(Box::new(density));
+        self
+    }
+
+    fn radius(&self, point: &Point<S>) -> Option<f64> {
+        let min_distance = self.min_distance.to_f64();
+        match &self.density {
+            Some(density) => {
+                let density = density(point).to_f64().min(1.0);
+                if density > 0.0 {
+                    Some(min_distance / density.sqrt())
+                } else {
+                    None
+                }
+            }
+            None => Some(min_distance),
+        }
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Point<S>> {
+        let mut points = Vec::new();
+        let min_distance = self.min_distance.to_f64();
+        if min_distance <= 0.0 {
+            return points;
+        }
+
+        let ((x0, y0), (x1, y1)) = self.bounds.extent();
+        let size = min_distance / 2f64.sqrt();
+        let columns = ((x1 - x0) / size).ceil().max(1.0) as usize;
+        let rows = ((y1 - y0) / size).ceil().max(1.0) as usize;
+        let mut grid = vec![usize::MAX; columns * rows];
+        let cell = |x: f64, y: f64| {
+            (
+                (((x - x0) / size) as usize).min(columns - 1),
+                (((y - y0) / size) as usize).min(rows - 1),
+            )
+        };
+        let at = |x: f64, y: f64| Point::new(S::from_f64(x), S::from_f64(y));
+
+        let mut coords: Vec<(f64, f64, f64)> = Vec::new();
+        let mut active = Vec::new();
+        let mut accept =
+            |x: f64, y: f64, coords: &mut Vec<(f64, f64, f64)>, active: &mut Vec<usize>| {
+                let point = at(x, y);
+                if !self.bounds.contains(&point) {
+                    return;
+                }
+                let radius = match self.radius(&point) {
+                    Some(radius) => radius,
+                    None => return,
+                };
+
+                let (column, row) = cell(x, y);
+                let reach = (radius / size).ceil() as usize;
+                for j in row.saturating_sub(reach)..(row + reach + 1).min(rows) {
+                    for i in column.saturating_sub(reach)..(column + reach + 1).min(columns) {
+                        let other = grid[j * columns + i];
+                        if other != usize::MAX {
+                            let (ox, oy, _) = coords[other];
+                            if (ox - x).hypot(oy - y) < radius {
+                                return;
+                            }
+                        }
+                    }
+                }
+
+                grid[row * columns + column] = coords.len();
+                active.push(coords.len());
+                coords.push((x, y, radius));
+                points.push(point);
+            };
+
+        for _ in 0..self.attempts.max(1) * 10 {
+            let x = x0 + rng.gen::<f64>() * (x1 - x0);
+            let y = y0 + rng.gen::<f64>() * (y1 - y0);
+            accept(x, y, &mut coords, &mut active);
+            if !coords.is_empty() {
+                break;
+            }
+        }
+
+        while !active.is_empty() {
+            let index = rng.gen_range(0..active.len());
+            let (x, y, radius) = coords[active[index]];
+            let count = coords.len();
+
+            for _ in 0..self.attempts {
+                let angle = rng.gen::<f64>() * std::f64::consts::TAU;
+                let (dx, dy) = (angle.cos(), angle.sin());
+                let step = 1.0 + rng.gen::<f64>();
+
+                // Stepping into a sparser region must clear the candidate's own radius, which
+                // may exceed twice the parent's.
+                let mut distance = radius * step;
+                if let Some(other) = self.radius(&at(x + distance * dx, y + distance * dy)) {
+                    distance = radius.max(other) * step;
+                }
+                accept(
+                    x + distance * dx,
+                    y + distance * dy,
+                    &mut coords,
+                    &mut active,
+                );
+                if coords.len() > count {
+                    break;
+                }
+            }
+
+            if coords.len() == count {
+                active.swap_remove(index);
+            }
+        }
+
+        points
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{Delaunay, Float};
+use std::fmt::{self, Display};
+
+/// Triangles with an angle below this many degrees count as slivers.
+const SLIVER_ANGLE: f64 = 10.0;
+
+/// Counts of values in consecutive bins: bin `i` spans `bounds[i]..bounds[i + 1]`, the last
+/// one including its upper bound.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Histogram {
+    pub bounds: Vec<f64>,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    fn new(values: &[f64], bounds: Vec<f64>) -> Self {
+        let bins = bounds.len().saturating_sub(1);
+        let mut counts = vec![0; bins];
+        for &value in values {
+            // First bound above the value, clamped so the extremes land in the outer bins.
+            let bin = bounds.partition_point(|&bound| bound <= value);
+            if let Some(count) = counts.get_mut(bin.clamp(1, bins) - 1) {
+                *count += 1;
+            }
+        }
+        Histogram { bounds, counts }
+    }
+
+    // Evenly spaced bins between `min` and `max`, or a single one when they are equal.
+    fn linear(values: &[f64], min: f64, max: f64, bins: usize) -> Self {
+        let bins = if max > min { bins } else { 1 };
+        let bounds = (0..=bins)
+            .map(|i| min + (max - min) * i as f64 / bins as f64)
+            .collect();
+        Histogram::new(values, bounds)
+    }
+}
+
+impl Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(4);
+        let width = self
+            .counts
+            .iter()
+            .max()
+            .map_or(1, |max| max.to_string().len());
+        for (i, count) in self.counts.iter().enumerate() {
+            let close = if i + 1 == self.counts.len() { ']' } else { ')' };
+            writeln!(
+                f,
+                "  [{:.*}, {:.*}{} {:>width$}",
+                precision,
+                self.bounds[i],
+                precision,
+                self.bounds[i + 1],
+                close,
+                count,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Summary of a set of values. All fields are zero when there are none.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub histogram: Histogram,
+}
+
+impl Summary {
+    fn new(values: &[f64], histogram: impl FnOnce(f64, f64) -> Histogram) -> Self {
+        if values.is_empty() {
+            return Summary::default();
+        }
+
+        let count = values.len();
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mean = values.iter().sum::<f64>() / count as f64;
+        let variance = values
+            .iter()
+            .map(|value| (value - mean) * (value - mean))
+            .sum::<f64>()
+            / count as f64;
+
+        Summary {
+            count,
+            min,
+            max,
+            mean,
+            std_dev: variance.sqrt(),
+            histogram: histogram(min, max),
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(4);
+        writeln!(
+            f,
+            "min {:.*}, max {:.*}, mean {:.*}, std dev {:.*}",
+            precision, self.min, precision, self.max, precision, self.mean, precision, self.std_dev
+        )?;
+        write!(f, "{:.*}", precision, self.histogram)
+    }
+}
+
+/// Shape statistics of the finite triangles of a triangulation, with angles in degrees.
+/// Printed with four decimals unless a precision is given, as in `{:.8}`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QualityReport {
+    pub triangles: usize,
+    /// Every interior angle of every triangle.
+    pub angles: Summary,
+    /// Circumradius over twice the inradius, 1 for an equilateral triangle.
+    pub aspect_ratios: Summary,
+    pub areas: Summary,
+    /// Lengths of the edges between two finite vertices.
+    pub edge_lengths: Summary,
+    pub sliver_angle: f64,
+    /// Triangles with an angle below `sliver_angle`, or with collinear corners.
+    pub slivers: usize,
+}
+
+impl Display for QualityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(4);
+        writeln!(f, "triangles: {}", self.triangles)?;
+        write!(f, "angles: {:.*}", precision, self.angles)?;
+        write!(f, "aspect ratios: {:.*}", precision, self.aspect_ratios)?;
+        write!(f, "areas: {:.*}", precision, self.areas)?;
+        write!(f, "edge lengths: {:.*}", precision, self.edge_lengths)?;
+        writeln!(
+            f,
+            "slivers (angle < {}°): {}",
+            self.sliver_angle, self.slivers
+        )
+    }
+}
+
+impl<S: Float> Delaunay<S> {
+    pub fn quality_report(&self) -> QualityReport {
+        let mut angles = Vec::new();
+        let mut aspect_ratios = Vec::new();
+        let mut areas = Vec::new();
+        let mut slivers = 0;
+
+        for face in self.faces() {
+            let (area, corners) = match (face.area(), face.angles()) {
+                (Some(area), Some(corners)) => (area.to_f64(), corners),
+                _ => continue,
+            };
+            areas.push(area);
+
+            let corners = corners
+                .into_iter()
+                .map(|angle| angle.to_f64().to_degrees())
+                .collect::<Vec<_>>();
+            let aspect_ratio = face.aspect_ratio().map(S::to_f64);
+            if aspect_ratio.is_none() || corners.iter().any(|&angle| angle < SLIVER_ANGLE) {
+                slivers += 1;
+            }
+            aspect_ratios.extend(aspect_ratio);
+            angles.extend(corners);
+        }
+
+        let edge_lengths = self
+            .edges()
+            .filter_map(|edge| edge.length())
+            .map(S::to_f64)
+            .collect::<Vec<_>>();
+
+        QualityReport {
+            triangles: areas.len(),
+            angles: Summary::new(&angles, |_, _| {
+                Histogram::new(&angles, (0..=12).map(|i| f64::from(i) * 15.0).collect())
+            }),
+            aspect_ratios: Summary::new(&aspect_ratios, |_, max| {
+                let mut bounds = vec![1.0, 1.25, 1.5, 2.0, 3.0, 5.0, 10.0];
+                if max > 10.0 {
+                    bounds.push(max);
+                }
+                Histogram::new(&aspect_ratios, bounds)
+            }),
+            areas: Summary::new(&areas, |min, max| Histogram::linear(&areas, min, max, 10)),
+            edge_lengths: Summary::new(&edge_lengths, |min, max| {
+                Histogram::linear(&edge_lengths, min, max, 10)
+            }),
+            sliver_angle: SLIVER_ANGLE,
+            slivers,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::graph::{Graph, GraphEdge, GraphFace, GraphVertex};
+use crate::{Attributes, Delaunay, EdgeId, FaceId, Float, HalfEdgeId, Point, Ray, Vector};
+use crate::{VertexId, Voronoi};
+use serde::de::Error as _;
+use serde::ser::{Error as _, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+impl<S: Float + Serialize> Serialize for Point<S> {
+    #[inline]
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        (self.x, self.y).serialize(serializer)
+    }
+}
+
+impl<'de, S: Float + Deserialize<'de>> Deserialize<'de> for Point<S> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (x, y) = <(S, S)>::deserialize(deserializer)?;
+        Ok(Point::new(x, y))
+    }
+}
+
+impl<S: Float + Serialize> Serialize for Vector<S> {
+    #[inline]
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        (self.x, self.y).serialize(serializer)
+    }
+}
+
+impl<'de, S: Float + Deserialize<'de>> Deserialize<'de> for Vector<S> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (x, y) = <(S, S)>::deserialize(deserializer)?;
+        Ok(Vector::new(x, y))
+    }
+}
+
+// Attribute columns are type-erased, so only those holding one of the types below are
+// serialized, tagged with their type. A graph with any other column fails to serialize
+// instead of silently losing it.
+macro_rules! columns {
+    ($($variant:ident($type:ty)),* $(,)?) => {
+        #[derive(Serialize)]
+        #[serde(rename = "Column")]
+        enum ColumnRef<'a> {
+            $($variant(&'a [$type]),)*
+        }
+
+        #[derive(Deserialize)]
+        enum Column {
+            $($variant(Vec<$type>),)*
+        }
+
+        impl<'a> ColumnRef<'a> {
+            #[inline]
+            fn new(column: &'a dyn Any) -> Option<Self> {
+                $(
+                    if let Some(values) = column.downcast_ref::<Vec<$type>>() {
+                        return Some(ColumnRef::$variant(values));
+                    }
+                )*
+                None
+            }
+        }
+
+        impl Column {
+            #[inline]
+            fn insert_into(self, attributes: &mut Attributes, name: &str) -> bool {
+                match self {
+                    $(Column::$variant(values) => attributes.insert(name, values).is_ok(),)*
+                }
+            }
+        }
+    };
+}
+
+columns! {
+    Bool(bool),
+    I32(i32),
+    I64(i64),
+    U32(u32),
+    U64(u64),
+    Usize(usize),
+    F32(f32),
+    F64(f64),
+    String(String),
+    EdgeId(EdgeId),
+    FaceId(FaceId),
+    HalfEdgeId(HalfEdgeId),
+    VertexId(VertexId),
+}
+
+struct Columns<'a>(&'a Attributes);
+
+impl Serialize for Columns<'_> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        let columns = self
+            .0
+            .columns()
+            .map(|(name, column)| match ColumnRef::new(column) {
+                Some(column) => Ok((name, column)),
+                None => Err(Z::Error::custom(format!(
+                    "attribute `{}` is not of a serializable type",
+                    name
+                ))),
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        columns.serialize(serializer)
+    }
+}
+
+impl<T, S> Serialize for Graph<T, S>
+where
+    T: Debug + Copy + Serialize,
+    S: Float + Serialize,
+{
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        let mut state = serializer.serialize_struct("Graph", 7)?;
+        state.serialize_field("points", &self.points)?;
+        state.serialize_field("edges", &self.edges)?;
+        state.serialize_field("faces", &self.faces)?;
+        state.serialize_field("vertices", &self.vertices)?;
+        state.serialize_field("edge_attributes", &Columns(&self.edge_attributes))?;
+        state.serialize_field("face_attributes", &Columns(&self.face_attributes))?;
+        state.serialize_field("vertex_attributes", &Columns(&self.vertex_attributes))?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Graph")]
+struct Parts<T: Debug + Copy, S: Float> {
+    points: Vec<Point<S>>,
+    edges: Vec<GraphEdge>,
+    faces: Vec<GraphFace>,
+    vertices: Vec<GraphVertex<T>>,
+    #[serde(default)]
+    edge_attributes: BTreeMap<String, Column>,
+    #[serde(default)]
+    face_attributes: BTreeMap<String, Column>,
+    #[serde(default)]
+    vertex_attributes: BTreeMap<String, Column>,
+}
+
+impl<'de, T, S> Deserialize<'de> for Graph<T, S>
+where
+    T: Debug + Copy + Deserialize<'de>,
+    S: Float + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let parts = Parts::<T, S>::deserialize(deserializer)?;
+        let mut graph = Graph::new(parts.points, parts.edges, parts.faces, parts.vertices);
+        graph.validate().map_err(D::Error::custom)?;
+
+        for (attributes, columns) in [
+            (&mut graph.edge_attributes, parts.edge_attributes),
+            (&mut graph.face_attributes, parts.face_attributes),
+            (&mut graph.vertex_attributes, parts.vertex_attributes),
+        ] {
+            for (name, column) in columns {
+                if !column.insert_into(attributes, &name) {
+                    return Err(D::Error::custom(format!(
+                        "attribute `{}` does not hold one value per element",
+                        name
+                    )));
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+// Beyond `Graph::validate`, a triangulation must hold only triangles around a single infinite
+// vertex 0, and a Voronoi diagram rays that match their cells, or building on them panics.
+impl<'de, S: Float + Deserialize<'de>> Deserialize<'de> for Delaunay<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let graph = Graph::<(), S>::deserialize(deserializer)?;
+        Delaunay::from_graph(graph).map_err(D::Error::custom)
+    }
+}
+
+impl<'de, S: Float + Deserialize<'de>> Deserialize<'de> for Voronoi<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let graph = Graph::<Ray<S>, S>::deserialize(deserializer)?;
+        Voronoi::from_graph(graph).map_err(D::Error::custom)
+    }
+}
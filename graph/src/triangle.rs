@@ -0,0 +1,536 @@
+//! Readers and writers for the `.node`, `.ele`, `.edge`, `.neigh` and `.poly` files of
+//! Shewchuk's Triangle. Readers take the numbering base from the first node, as Triangle
+//! does, and hand back zero-based indices; writers number from 1.
+
+use crate::graph::Graph;
+use crate::{Attribute, FaceId, Float, Point};
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// Vertices of a `.node` file, or of the vertex section of a `.poly` file.
+#[derive(Clone, Debug)]
+pub struct Nodes<S: Float = f64> {
+    pub points: Vec<Point<S>>,
+    /// Attribute values of every node, one `Vec` per attribute.
+    pub attributes: Vec<Vec<S>>,
+    pub markers: Option<Vec<i32>>,
+    /// Number of the first node, 0 or 1.
+    pub first: usize,
+}
+
+/// Segments of a `.edge` or `.poly` file as zero-based node pairs.
+#[derive(Clone, Debug, Default)]
+pub struct Segments {
+    pub ends: Vec<[usize; 2]>,
+    pub markers: Option<Vec<i32>>,
+}
+
+/// Planar straight-line graph of a `.poly` file.
+///
+/// This crate has no constrained triangulation, so the segments and holes are only carried
+/// along; triangulating the nodes gives their unconstrained Delaunay triangulation.
+#[derive(Clone, Debug)]
+pub struct Poly<S: Float = f64> {
+    pub nodes: Nodes<S>,
+    pub segments: Segments,
+    pub holes: Vec<Point<S>>,
+}
+
+struct Tokens<R> {
+    reader: R,
+    line: usize,
+}
+
+impl<R: BufRead> Tokens<R> {
+    #[inline]
+    fn new(reader: R) -> Self {
+        Tokens { reader, line: 0 }
+    }
+
+    // Next line with any content, split on whitespace with the comment removed.
+    fn next(&mut self) -> io::Result<Vec<String>> {
+        let mut buffer = String::new();
+        loop {
+            buffer.clear();
+            if self.reader.read_line(&mut buffer)? == 0 {
+                return Err(self.error("unexpected end of file"));
+            }
+            self.line += 1;
+
+            let content = buffer.split('#').next().unwrap_or_default();
+            let tokens = content
+                .split_whitespace()
+                .map(str::to_owned)
+                .collect::<Vec<_>>();
+            if !tokens.is_empty() {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    #[inline]
+    fn error(&self, message: impl Display) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {}: {}", self.line, message),
+        )
+    }
+
+    fn field<T: FromStr>(&self, tokens: &[String], index: usize) -> io::Result<T> {
+        let token = tokens
+            .get(index)
+            .ok_or_else(|| self.error(format!("missing field {}", index + 1)))?;
+        token
+            .parse()
+            .map_err(|_| self.error(format!("invalid field {} `{}`", index + 1, token)))
+    }
+
+    #[inline]
+    fn optional<T: FromStr>(&self, tokens: &[String], index: usize, default: T) -> io::Result<T> {
+        if index < tokens.len() {
+            self.field(tokens, index)
+        } else {
+            Ok(default)
+        }
+    }
+
+    #[inline]
+    fn scalar<S: Float>(&self, tokens: &[String], index: usize) -> io::Result<S> {
+        self.field::<f64>(tokens, index).map(S::from_f64)
+    }
+
+    // Checks the running number of a record against its expected value.
+    #[inline]
+    fn number(&self, tokens: &[String], expected: usize) -> io::Result<()> {
+        let number = self.field::<usize>(tokens, 0)?;
+        if number == expected {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected number {}, found {}", expected, number)))
+        }
+    }
+
+    fn node(
+        &self,
+        tokens: &[String],
+        nodes: &Nodes<impl Float>,
+        index: usize,
+    ) -> io::Result<usize> {
+        let node = self.field::<usize>(tokens, index)?;
+        match node.checked_sub(nodes.first) {
+            Some(node) if node < nodes.points.len() => Ok(node),
+            _ => Err(self.error(format!("node {} out of range", node))),
+        }
+    }
+}
+
+fn read_nodes<R: BufRead, S: Float>(
+    tokens: &mut Tokens<R>,
+    header: &[String],
+) -> io::Result<Nodes<S>> {
+    let count = tokens.field::<usize>(header, 0)?;
+    let dimension = tokens.optional::<usize>(header, 1, 2)?;
+    if dimension != 2 {
+        return Err(tokens.error(format!("dimension {} is not 2", dimension)));
+    }
+    let attributes = tokens.optional::<usize>(header, 2, 0)?;
+    let markers = tokens.optional::<usize>(header, 3, 0)? > 0;
+
+    let mut nodes = Nodes {
+        points: Vec::with_capacity(count),
+        attributes: vec![Vec::with_capacity(count); attributes],
+        markers: if markers {
+            Some(Vec::with_capacity(count))
+        } else {
+            None
+        },
+        first: 0,
+    };
+
+    for i in 0..count {
+        let line = tokens.next()?;
+        if i == 0 {
+            nodes.first = tokens.field(&line, 0)?;
+        }
+        tokens.number(&line, nodes.first + i)?;
+
+        nodes.points.push(Point::new(
+            tokens.scalar(&line, 1)?,
+            tokens.scalar(&line, 2)?,
+        ));
+        for (j, values) in nodes.attributes.iter_mut().enumerate() {
+            values.push(tokens.scalar(&line, 3 + j)?);
+        }
+        if let Some(markers) = &mut nodes.markers {
+            markers.push(tokens.optional(&line, 3 + attributes, 0)?);
+        }
+    }
+
+    Ok(nodes)
+}
+
+fn read_segments<R: BufRead, S: Float>(
+    tokens: &mut Tokens<R>,
+    nodes: &Nodes<S>,
+) -> io::Result<Segments> {
+    let header = tokens.next()?;
+    let count = tokens.field::<usize>(&header, 0)?;
+    let markers = tokens.optional::<usize>(&header, 1, 0)? > 0;
+
+    let mut segments = Segments {
+        ends: Vec::with_capacity(count),
+        markers: if markers {
+            Some(Vec::with_capacity(count))
+        } else {
+            None
+        },
+    };
+
+    for _ in 0..count {
+        let line = tokens.next()?;
+        segments
+            .ends
+            .push([tokens.node(&line, nodes, 1)?, tokens.node(&line, nodes, 2)?]);
+        if let Some(markers) = &mut segments.markers {
+            markers.push(tokens.optional(&line, 3, 0)?);
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Reads a `.node` file. Pass its points to [`Delaunay::from`](crate::Delaunay::from) to
+/// triangulate them.
+pub fn read_node<R: BufRead, S: Float>(reader: R) -> io::Result<Nodes<S>> {
+    let mut tokens = Tokens::new(reader);
+    let header = tokens.next()?;
+    read_nodes(&mut tokens, &header)
+}
+
+// Values of the `kept` nodes as a vertex column, `zero` on the infinite vertices, which come
+// first and after the nodes.
+fn column<A: Copy>(values: &[A], kept: &[usize], len: usize, zero: A) -> Vec<A> {
+    let mut column = std::iter::once(zero)
+        .chain(kept.iter().map(|&node| values[node]))
+        .collect::<Vec<_>>();
+    column.resize(len, zero);
+    column
+}
+
+/// Reads a `.ele` file over `nodes` into a graph. Vertices 1, 2, … are the nodes used by some
+/// triangle, in node order, so unused nodes are left out. The triangles come first in file
+/// order, the outer boundary is closed on the infinite vertex 0 and each hole on an infinite
+/// vertex of its own after the nodes. Node markers and attributes become the vertex
+/// attributes `"marker"` and `"attribute0"`, `"attribute1"`, …, and triangle attributes the
+/// face attributes of the same names.
+///
+/// Clockwise triangles are turned around. Quadratic triangles keep their corners only.
+pub fn read_ele<R: BufRead, S: Float + Attribute>(
+    reader: R,
+    nodes: &Nodes<S>,
+) -> io::Result<Graph<(), S>> {
+    let mut tokens = Tokens::new(reader);
+    let header = tokens.next()?;
+    let count = tokens.field::<usize>(&header, 0)?;
+    let corners = tokens.optional::<usize>(&header, 1, 3)?;
+    if corners != 3 && corners != 6 {
+        return Err(tokens.error(format!("{} nodes per triangle", corners)));
+    }
+    let attributes = tokens.optional::<usize>(&header, 2, 0)?;
+
+    let mut triangles = Vec::with_capacity(count);
+    let mut values = vec![Vec::with_capacity(count); attributes];
+    for _ in 0..count {
+        let line = tokens.next()?;
+        let mut triangle = vec![
+            tokens.node(&line, nodes, 1)?,
+            tokens.node(&line, nodes, 2)?,
+            tokens.node(&line, nodes, 3)?,
+        ];
+        if triangle[0] == triangle[1] || triangle[1] == triangle[2] || triangle[2] == triangle[0] {
+            return Err(tokens.error("triangle repeats a node"));
+        }
+        let area = crate::cell::signed_area(&[
+            nodes.points[triangle[0]],
+            nodes.points[triangle[1]],
+            nodes.points[triangle[2]],
+        ]);
+        if area == 0.0 {
+            return Err(tokens.error("triangle has no area"));
+        }
+        if area < 0.0 {
+            triangle.swap(1, 2);
+        }
+        triangles.push(triangle);
+
+        for (j, values) in values.iter_mut().enumerate() {
+            values.push(tokens.scalar(&line, 1 + corners + j)?);
+        }
+    }
+
+    let mut used = vec![usize::MAX; nodes.points.len()];
+    let mut kept = Vec::new();
+    for &node in triangles.iter().flatten() {
+        if used[node] == usize::MAX {
+            used[node] = 0;
+            kept.push(node);
+        }
+    }
+    kept.sort_unstable();
+    for (i, &node) in kept.iter().enumerate() {
+        used[node] = i;
+    }
+    for triangle in &mut triangles {
+        triangle.iter_mut().for_each(|node| *node = used[*node]);
+    }
+
+    let invalid = |error| io::Error::new(io::ErrorKind::InvalidData, error);
+    let points = kept.iter().map(|&node| nodes.points[node]).collect();
+    let mut graph = Graph::from_polygons(points, &triangles).map_err(invalid)?;
+    graph.validate().map_err(invalid)?;
+
+    let face_count = graph.face_count();
+    let vertex_count = graph.vertex_count();
+    let vertices = graph.vertex_attributes_mut();
+    if let Some(markers) = &nodes.markers {
+        let values = column(markers, &kept, vertex_count, 0);
+        vertices.insert("marker", values).unwrap();
+    }
+    for (j, values) in nodes.attributes.iter().enumerate() {
+        let values = column(values, &kept, vertex_count, S::zero());
+        vertices.insert(&format!("attribute{}", j), values).unwrap();
+    }
+    for (j, mut values) in values.into_iter().enumerate() {
+        values.resize(face_count, S::zero());
+        graph
+            .face_attributes_mut()
+            .insert(&format!("attribute{}", j), values)
+            .unwrap();
+    }
+
+    Ok(graph)
+}
+
+/// Reads a `.edge` file over `nodes`.
+pub fn read_edge<R: BufRead, S: Float>(reader: R, nodes: &Nodes<S>) -> io::Result<Segments> {
+    read_segments(&mut Tokens::new(reader), nodes)
+}
+
+/// Reads a `.neigh` file numbered like `nodes`: the neighbours of each triangle opposite its
+/// three corners, `None` on the hull.
+pub fn read_neigh<R: BufRead, S: Float>(
+    reader: R,
+    nodes: &Nodes<S>,
+) -> io::Result<Vec<[Option<usize>; 3]>> {
+    let mut tokens = Tokens::new(reader);
+    let header = tokens.next()?;
+    let count = tokens.field::<usize>(&header, 0)?;
+
+    let mut neighbors = Vec::with_capacity(count);
+    for _ in 0..count {
+        let line = tokens.next()?;
+        let mut triangle = [None; 3];
+        for (j, neighbor) in triangle.iter_mut().enumerate() {
+            let index = tokens.field::<i64>(&line, 1 + j)?;
+            if index >= 0 {
+                let index = (index as usize)
+                    .checked_sub(nodes.first)
+                    .filter(|&index| index < count)
+                    .ok_or_else(|| tokens.error(format!("triangle {} out of range", index)))?;
+                *neighbor = Some(index);
+            }
+        }
+        neighbors.push(triangle);
+    }
+
+    Ok(neighbors)
+}
+
+/// Reads a `.poly` file. Files that list no vertices refer to a separate `.node` file, which
+/// must then be passed as `nodes`.
+pub fn read_poly<R: BufRead, S: Float>(reader: R, nodes: Option<&Nodes<S>>) -> io::Result<Poly<S>> {
+    let mut tokens = Tokens::new(reader);
+    let header = tokens.next()?;
+    let mut own = read_nodes(&mut tokens, &header)?;
+    if own.points.is_empty() {
+        own = nodes
+            .cloned()
+            .ok_or_else(|| tokens.error("no vertices and no .node file"))?;
+    }
+
+    let segments = read_segments(&mut tokens, &own)?;
+
+    let header = tokens.next()?;
+    let count = tokens.field::<usize>(&header, 0)?;
+    let mut holes = Vec::with_capacity(count);
+    for _ in 0..count {
+        let line = tokens.next()?;
+        holes.push(Point::new(
+            tokens.scalar(&line, 1)?,
+            tokens.scalar(&line, 2)?,
+        ));
+    }
+
+    Ok(Poly {
+        nodes: own,
+        segments,
+        holes,
+    })
+}
+
+// One-based numbers of the finite vertices, 0 for the infinite one.
+fn numbering<S: Float>(graph: &Graph<(), S>) -> Vec<usize> {
+    let mut next = 0;
+    graph
+        .vertices()
+        .map(|vertex| {
+            if vertex.is_infinite() {
+                0
+            } else {
+                next += 1;
+                next
+            }
+        })
+        .collect()
+}
+
+// Finite triangles of the graph as face ids and corner vertex ids, erroring on any finite
+// polygon. Flat triangles, which the hull keeps over collinear input, are left out.
+fn triangles<S: Float>(graph: &Graph<(), S>) -> io::Result<Vec<(usize, [usize; 3])>> {
+    let mut triangles = Vec::new();
+    for face in graph.faces().filter(|face| !face.is_infinite()) {
+        let corners = face.vertices().collect::<Vec<_>>();
+        let [a, b, c] = match corners[..] {
+            [a, b, c] => [a, b, c],
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("face {} is not a triangle", face.id()),
+                ))
+            }
+        };
+        let points = [a, b, c].map(|vertex| vertex.position().left());
+        if let [Some(p0), Some(p1), Some(p2)] = points {
+            if !Point::is_ccw(&p0, &p1, &p2) && !Point::is_ccw(&p0, &p2, &p1) {
+                continue;
+            }
+        }
+        let ids = [a, b, c].map(|vertex| vertex.id().index());
+        triangles.push((face.id().index(), ids));
+    }
+    Ok(triangles)
+}
+
+fn write_nodes<S: Float, W: Write>(graph: &Graph<(), S>, writer: &mut W) -> io::Result<()> {
+    let count = graph.vertices().filter(|v| !v.is_infinite()).count();
+    writeln!(writer, "{} 2 0 1", count)?;
+    for (vertex, number) in graph.vertices().zip(numbering(graph)) {
+        if let Some(point) = vertex.position().left() {
+            let hull = vertex.neighbors().any(|neighbor| neighbor.is_infinite());
+            writeln!(
+                writer,
+                "{} {} {} {}",
+                number,
+                point.x.to_f64(),
+                point.y.to_f64(),
+                hull as u8
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the finite vertices of `graph` as a `.node` file, marking hull vertices with 1.
+pub fn write_node<S: Float, W: Write>(graph: &Graph<(), S>, mut writer: W) -> io::Result<()> {
+    write_nodes(graph, &mut writer)?;
+    writer.flush()
+}
+
+/// Writes the finite triangles of `graph` as a `.ele` file, failing on any other polygon.
+/// Zero-area triangles along a collinear stretch of the hull are skipped, as Triangle never
+/// emits them and [`read_ele`] rejects them.
+pub fn write_ele<S: Float, W: Write>(graph: &Graph<(), S>, mut writer: W) -> io::Result<()> {
+    let numbers = numbering(graph);
+    let triangles = triangles(graph)?;
+    writeln!(writer, "{} 3 0", triangles.len())?;
+    for (i, (_, [a, b, c])) in triangles.into_iter().enumerate() {
+        writeln!(
+            writer,
+            "{} {} {} {}",
+            i + 1,
+            numbers[a],
+            numbers[b],
+            numbers[c]
+        )?;
+    }
+    writer.flush()
+}
+
+/// Writes the finite edges of `graph` as a `.edge` file, marking hull edges with 1.
+pub fn write_edge<S: Float, W: Write>(graph: &Graph<(), S>, mut writer: W) -> io::Result<()> {
+    let numbers = numbering(graph);
+    let edges = graph
+        .edges()
+        .filter(|edge| !edge.is_infinite())
+        .collect::<Vec<_>>();
+    writeln!(writer, "{} 1", edges.len())?;
+    for (i, edge) in edges.into_iter().enumerate() {
+        let hull = edge.left_face().is_infinite() || edge.right_face().is_infinite();
+        writeln!(
+            writer,
+            "{} {} {} {}",
+            i + 1,
+            numbers[edge.origin().id().index()],
+            numbers[edge.destination().id().index()],
+            hull as u8
+        )?;
+    }
+    writer.flush()
+}
+
+/// Writes the neighbours of the finite triangles of `graph` as a `.neigh` file, numbered like
+/// [`write_ele`], with -1 across the hull and across the skipped flat triangles.
+pub fn write_neigh<S: Float, W: Write>(graph: &Graph<(), S>, mut writer: W) -> io::Result<()> {
+    let triangles = triangles(graph)?;
+    let mut numbers = vec![-1; graph.face_count()];
+    for (i, &(face, _)) in triangles.iter().enumerate() {
+        numbers[face] = i as i64 + 1;
+    }
+
+    writeln!(writer, "{} 3", triangles.len())?;
+    for &(face, _) in &triangles {
+        let edges = graph.face(FaceId::new(face)).edges().collect::<Vec<_>>();
+        write!(writer, "{}", numbers[face])?;
+        for i in 0..3 {
+            let neighbor = edges[(i + 1) % 3].right_face();
+            write!(writer, " {}", numbers[neighbor.id().index()])?;
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+/// Writes the finite vertices of `graph` and its hull as segments to a `.poly` file.
+pub fn write_poly<S: Float, W: Write>(graph: &Graph<(), S>, mut writer: W) -> io::Result<()> {
+    write_nodes(graph, &mut writer)?;
+
+    let numbers = numbering(graph);
+    let hull = graph
+        .edges()
+        .filter(|edge| !edge.is_infinite())
+        .filter(|edge| edge.left_face().is_infinite() || edge.right_face().is_infinite())
+        .collect::<Vec<_>>();
+    writeln!(writer, "{} 1", hull.len())?;
+    for (i, edge) in hull.into_iter().enumerate() {
+        writeln!(
+            writer,
+            "{} {} {} 1",
+            i + 1,
+            numbers[edge.origin().id().index()],
+            numbers[edge.destination().id().index()]
+        )?;
+    }
+    writeln!(writer, "0")?;
+    writer.flush()
+}
@@ -0,0 +1,395 @@
+use crate::graph::{Graph, GraphEdge};
+use crate::{EdgeId, FaceId, Float, HalfEdgeId, Point, VertexId};
+use std::fmt::{self, Debug, Display};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    OddEdgeCount,
+    DanglingEdge(HalfEdgeId),
+    BrokenNext(HalfEdgeId),
+    BrokenFace(FaceId),
+    BrokenVertex(VertexId),
+    UncoveredEdges,
+    Clockwise(FaceId),
+    Flat(FaceId),
+    Euler(isize),
+    NotTriangle(FaceId),
+    Infinite(VertexId),
+    OpenHull(EdgeId),
+    BrokenRay(VertexId),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::OddEdgeCount => write!(f, "half-edges are not paired"),
+            ValidationError::DanglingEdge(id) => write!(f, "half-edge {} points out of range", id),
+            ValidationError::BrokenNext(id) => write!(f, "half-edge {} does not chain", id),
+            ValidationError::BrokenFace(id) => write!(f, "face {} has an inconsistent cycle", id),
+            ValidationError::BrokenVertex(id) => {
+                write!(f, "vertex {} has an inconsistent ring", id)
+            }
+            ValidationError::UncoveredEdges => write!(f, "half-edges belong to no face or ring"),
+            ValidationError::Clockwise(id) => write!(f, "face {} is clockwise", id),
+            ValidationError::Flat(id) => write!(f, "face {} is flat away from the hull", id),
+            ValidationError::Euler(chi) => write!(f, "Euler characteristic is {}, not 2", chi),
+            ValidationError::NotTriangle(id) => write!(f, "face {} is not a triangle", id),
+            ValidationError::Infinite(id) => {
+                write!(f, "vertex {} breaks the single infinite vertex 0", id)
+            }
+            ValidationError::OpenHull(id) => {
+                write!(f, "edge {} has infinite faces on both sides", id)
+            }
+            ValidationError::BrokenRay(id) => write!(f, "ray of vertex {} is inconsistent", id),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+// Read access to the half-edge arrays, shared by graphs and their on-disk views.
+pub(crate) trait Records {
+    fn half_edge_count(&self) -> usize;
+    fn face_count(&self) -> usize;
+    fn vertex_count(&self) -> usize;
+    fn point_count(&self) -> usize;
+
+    fn half_edge(&self, id: usize) -> GraphEdge;
+    fn face_edge(&self, id: usize) -> usize;
+    fn vertex_edge(&self, id: usize) -> usize;
+    /// Point of a vertex, `None` for a vertex without one.
+    fn vertex_point(&self, id: usize) -> Option<usize>;
+    fn point(&self, id: usize) -> (f64, f64);
+
+    #[inline]
+    fn next_of(&self, edge: usize) -> usize {
+        self.half_edge(edge).next() ^ 1
+    }
+
+    #[inline]
+    fn origin_of(&self, edge: usize) -> usize {
+        self.half_edge(edge).vertex()
+    }
+
+    #[inline]
+    fn left_of(&self, edge: usize) -> usize {
+        self.half_edge(edge ^ 1).face()
+    }
+}
+
+impl<T: Debug + Copy, S: Float> Records for Graph<T, S> {
+    #[inline]
+    fn half_edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[inline]
+    fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    #[inline]
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    #[inline]
+    fn point_count(&self) -> usize {
+        self.points.len()
+    }
+
+    #[inline]
+    fn half_edge(&self, id: usize) -> GraphEdge {
+        self.edges[id]
+    }
+
+    #[inline]
+    fn face_edge(&self, id: usize) -> usize {
+        self.faces[id].edge()
+    }
+
+    #[inline]
+    fn vertex_edge(&self, id: usize) -> usize {
+        self.vertices[id].edge()
+    }
+
+    #[inline]
+    fn vertex_point(&self, id: usize) -> Option<usize> {
+        self.vertices[id].position().left()
+    }
+
+    #[inline]
+    fn point(&self, id: usize) -> (f64, f64) {
+        (self.points[id].x.to_f64(), self.points[id].y.to_f64())
+    }
+}
+
+pub(crate) fn validate(records: &impl Records) -> Result<(), ValidationError> {
+    let len = records.half_edge_count();
+    let (face_count, vertex_count) = (records.face_count(), records.vertex_count());
+    if len & 1 != 0 {
+        return Err(ValidationError::OddEdgeCount);
+    }
+
+    for id in 0..len {
+        let edge = records.half_edge(id);
+        let in_range =
+            edge.next() < len && edge.face() < face_count && edge.vertex() < vertex_count;
+        if !in_range {
+            return Err(ValidationError::DanglingEdge(HalfEdgeId::new(id)));
+        }
+    }
+
+    for id in 0..len {
+        let next = records.next_of(id);
+        if records.origin_of(next) != records.origin_of(id ^ 1)
+            || records.left_of(next) != records.left_of(id)
+        {
+            return Err(ValidationError::BrokenNext(HalfEdgeId::new(id)));
+        }
+    }
+
+    let mut covered = 0;
+    for id in 0..face_count {
+        let first = records.face_edge(id);
+        if first >= len || records.left_of(first) != id {
+            return Err(ValidationError::BrokenFace(FaceId::new(id)));
+        }
+
+        let mut edge = first;
+        loop {
+            covered += 1;
+            edge = records.next_of(edge);
+            if edge == first {
+                break;
+            }
+            if covered > len {
+                return Err(ValidationError::BrokenFace(FaceId::new(id)));
+            }
+        }
+    }
+    if covered != len {
+        return Err(ValidationError::UncoveredEdges);
+    }
+
+    let mut covered = 0;
+    for id in 0..vertex_count {
+        let first = records.vertex_edge(id);
+        let in_range = match records.vertex_point(id) {
+            Some(point) => point < records.point_count(),
+            None => true,
+        };
+        if !in_range || first >= len || records.origin_of(first ^ 1) != id {
+            return Err(ValidationError::BrokenVertex(VertexId::new(id)));
+        }
+
+        let mut edge = first;
+        loop {
+            covered += 1;
+            edge = records.half_edge(edge).next();
+            if edge == first {
+                break;
+            }
+            if covered > len || records.origin_of(edge ^ 1) != id {
+                return Err(ValidationError::BrokenVertex(VertexId::new(id)));
+            }
+        }
+    }
+    if covered != len {
+        return Err(ValidationError::UncoveredEdges);
+    }
+
+    let mut positions = Vec::new();
+    for id in 0..face_count {
+        positions.clear();
+        let first = records.face_edge(id);
+        let mut edge = first;
+        let finite = loop {
+            match records.vertex_point(records.origin_of(edge)) {
+                Some(point) => positions.push(records.point(point)),
+                None => break false,
+            }
+            edge = records.next_of(edge);
+            if edge == first {
+                break true;
+            }
+        };
+
+        if finite {
+            // Measured from the first corner so that faces far from the origin keep their
+            // precision; flat triangles pass and are checked below.
+            let clockwise = match positions[..] {
+                [(x0, y0), (x1, y1), (x2, y2)] => Point::is_ccw(
+                    &Point::new(x0, y0),
+                    &Point::new(x2, y2),
+                    &Point::new(x1, y1),
+                ),
+                _ => {
+                    let (x0, y0) = positions[0];
+                    let area = (1..positions.len() - 1).fold(0.0, |area, i| {
+                        let (x1, y1) = (positions[i].0 - x0, positions[i].1 - y0);
+                        let (x2, y2) = (positions[i + 1].0 - x0, positions[i + 1].1 - y0);
+                        area + x1 * y2 - x2 * y1
+                    });
+                    area < 0.0
+                }
+            };
+            if clockwise {
+                return Err(ValidationError::Clockwise(FaceId::new(id)));
+            }
+        }
+    }
+
+    // Flat triangles only stack along the hull: across the longest side of each lies the
+    // infinite face or another flat triangle with a longer side still, never a real triangle
+    // it would overlap or fold onto.
+    for id in 0..face_count {
+        let side = match flat_side(records, records.face_edge(id)) {
+            Some(side) => side,
+            None => continue,
+        };
+        let twin = side ^ 1;
+        let nested = match flat_side(records, twin) {
+            Some(outer) => outer != twin,
+            None => cycle_is_infinite(records, twin),
+        };
+        if !nested {
+            return Err(ValidationError::Flat(FaceId::new(id)));
+        }
+    }
+
+    let chi = vertex_count as isize - (len / 2) as isize + face_count as isize;
+    if chi != 2 {
+        return Err(ValidationError::Euler(chi));
+    }
+
+    Ok(())
+}
+
+// What a `Delaunay` relies on beyond `validate`, which must have passed: every face is a
+// triangle with three distinct corners, vertex 0 is the only infinite vertex, and each hull
+// edge has a finite triangle on its other side.
+pub(crate) fn triangulation(records: &impl Records) -> Result<(), ValidationError> {
+    for id in 0..records.face_count() {
+        let e0 = records.face_edge(id);
+        let e1 = records.next_of(e0);
+        let e2 = records.next_of(e1);
+        let (v0, v1, v2) = (
+            records.origin_of(e0),
+            records.origin_of(e1),
+            records.origin_of(e2),
+        );
+        if records.next_of(e2) != e0 || v0 == v1 || v1 == v2 || v2 == v0 {
+            return Err(ValidationError::NotTriangle(FaceId::new(id)));
+        }
+    }
+
+    for id in 0..records.vertex_count() {
+        if records.vertex_point(id).is_none() != (id == 0) {
+            return Err(ValidationError::Infinite(VertexId::new(id)));
+        }
+    }
+
+    for edge in (0..records.half_edge_count()).step_by(2) {
+        let finite = records.origin_of(edge) != 0 && records.origin_of(edge ^ 1) != 0;
+        if finite && cycle_is_infinite(records, edge) && cycle_is_infinite(records, edge ^ 1) {
+            return Err(ValidationError::OpenHull(EdgeId::new(edge >> 1)));
+        }
+    }
+
+    Ok(())
+}
+
+// Longest side of the face of `edge` if it is a triangle with three collinear finite corners.
+fn flat_side(records: &impl Records, edge: usize) -> Option<usize> {
+    let edges = [
+        edge,
+        records.next_of(edge),
+        records.next_of(records.next_of(edge)),
+    ];
+    if records.next_of(edges[2]) != edge {
+        return None;
+    }
+
+    let mut corners = [Point::new(0.0, 0.0); 3];
+    for (corner, &edge) in corners.iter_mut().zip(&edges) {
+        let (x, y) = records.point(records.vertex_point(records.origin_of(edge))?);
+        *corner = Point::new(x, y);
+    }
+    let [p0, p1, p2] = corners;
+    if Point::is_ccw(&p0, &p1, &p2) || Point::is_ccw(&p0, &p2, &p1) {
+        return None;
+    }
+
+    let length = |i: usize| {
+        let (p, q) = (corners[i], corners[(i + 1) % 3]);
+        (q.x - p.x).hypot(q.y - p.y)
+    };
+    (0..3)
+        .max_by(|&i, &j| length(i).total_cmp(&length(j)))
+        .map(|i| edges[i])
+}
+
+fn cycle_is_infinite(records: &impl Records, first: usize) -> bool {
+    let mut edge = first;
+    loop {
+        if records.vertex_point(records.origin_of(edge)).is_none() {
+            return true;
+        }
+        edge = records.next_of(edge);
+        if edge == first {
+            return false;
+        }
+    }
+}
+
+// Circumcircle test for the quad around `edge`, skipping hull and non-convex quads and
+// counting cocircular ones as legal.
+pub(crate) fn is_illegal(records: &impl Records, edge: usize) -> bool {
+    let is_triangle = |edge| records.next_of(records.next_of(records.next_of(edge))) == edge;
+    if !is_triangle(edge) || !is_triangle(edge ^ 1) {
+        return false;
+    }
+
+    // In a triangle the edge after next is the previous one.
+    let opposite = |edge| records.origin_of(records.next_of(records.next_of(edge)));
+    let position = |vertex| {
+        let (x, y) = records.point(records.vertex_point(vertex)?);
+        Some(Point::new(x, y))
+    };
+
+    match (
+        position(records.origin_of(edge)),
+        position(records.origin_of(edge ^ 1)),
+        position(opposite(edge)),
+        position(opposite(edge ^ 1)),
+    ) {
+        (Some(pa), Some(pb), Some(pc), Some(pd)) => {
+            Point::strictly_in_circle(&pa, &pc, &pb, &pd)
+                && Point::is_ccw(&pd, &pc, &pa)
+                && Point::is_ccw(&pc, &pd, &pb)
+        }
+        _ => false,
+    }
+}
+
+pub(crate) fn verify(records: &impl Records) -> Result<(), Vec<EdgeId>> {
+    let illegal = (0..records.half_edge_count())
+        .step_by(2)
+        .filter(|&edge| is_illegal(records, edge))
+        .map(|edge| EdgeId::new(edge >> 1))
+        .collect::<Vec<_>>();
+
+    if illegal.is_empty() {
+        Ok(())
+    } else {
+        Err(illegal)
+    }
+}
+
+impl<T: Debug + Copy, S: Float> Graph<T, S> {
+    #[inline]
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate(self)
+    }
+}
@@ -1,15 +1,65 @@
-use crate::graph::{Graph, GraphEdge, GraphFace, GraphVertex};
+use crate::graph::Graph;
 use crate::Delaunay;
-use crate::{Point, Vector};
-use either::{Left, Right};
-use std::hint::unreachable_unchecked;
+use crate::VertexId;
+use crate::{Attribute, Attributes, Edge, EdgeId, Face, FaceId, Float, Point, Vector};
+use either::{Either, Left, Right};
 use std::ops::Deref;
 
+/// Unbounded end of a Voronoi edge: the edge leaves `origin` along `direction` and separates
+/// the cells of the two `sites`, consecutive Delaunay vertices on the convex hull.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray<S: Float = f64> {
+    origin: Point<S>,
+    direction: Vector<S>,
+    length: S,
+    sites: (VertexId, VertexId),
+}
+
+impl<S: Float> Ray<S> {
+    #[inline]
+    pub fn origin(&self) -> Point<S> {
+        self.origin
+    }
+
+    /// Unit vector pointing away from the hull.
+    #[inline]
+    pub fn direction(&self) -> Vector<S> {
+        self.direction
+    }
+
+    /// Length of the hull edge between the two sites.
+    #[inline]
+    pub fn length(&self) -> S {
+        self.length
+    }
+
+    #[inline]
+    pub fn sites(&self) -> (VertexId, VertexId) {
+        self.sites
+    }
+
+    // The same ray leaving `origin`, for edges that run parallel to it from another vertex.
+    #[inline]
+    pub(crate) fn with_origin(self, origin: Point<S>) -> Self {
+        Ray { origin, ..self }
+    }
+
+    #[inline]
+    pub fn at(&self, distance: S) -> Point<S> {
+        Point::new(
+            self.origin.x + self.direction.x * distance,
+            self.origin.y + self.direction.y * distance,
+        )
+    }
+}
+
 #[derive(Debug)]
-pub struct Voronoi(Graph<Vector>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Voronoi<S: Float = f64>(Graph<Ray<S>, S>);
 
-impl Deref for Voronoi {
-    type Target = Graph<Vector>;
+impl<S: Float> Deref for Voronoi<S> {
+    type Target = Graph<Ray<S>, S>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -17,63 +67,232 @@ impl Deref for Voronoi {
     }
 }
 
-impl From<&Delaunay> for Voronoi {
-    fn from(delaunay: &Delaunay) -> Self {
-        let edges = delaunay
-            .edges
-            .iter()
-            .map(|e| GraphEdge::new(e.face, e.next ^ 1, e.vertex))
-            .collect::<Vec<_>>();
-        let faces = delaunay
-            .vertices
-            .iter()
-            .map(|v| GraphFace::new(v.edge))
-            .collect::<Vec<_>>();
-        let mut points = Vec::with_capacity(delaunay.faces.len());
-        let mut vertices = Vec::with_capacity(delaunay.faces.len());
-
-        delaunay.faces.iter().for_each(|f| {
-            let e0 = f.edge;
-            let e1 = delaunay.edges[e0].next ^ 1;
-            let e2 = delaunay.edges[e1].next ^ 1;
-
-            let v0 = delaunay.edges[e0].vertex;
-            let v1 = delaunay.edges[e1].vertex;
-            let v2 = delaunay.edges[e2].vertex;
-
-            let p0 = delaunay.vertices[v0].position;
-            let p1 = delaunay.vertices[v1].position;
-            let p2 = delaunay.vertices[v2].position;
-
-            match (p0, p1, p2) {
-                (Left(i0), Left(i1), Left(i2)) => {
-                    let i = points.len();
-                    let c = Point::circumcenter(
-                        &delaunay.points[i0],
-                        &delaunay.points[i1],
-                        &delaunay.points[i2],
-                    );
-
-                    points.push(c);
-                    vertices.push(GraphVertex::new(e0, Left(i)));
-                }
-                (Left(i0), Left(i1), _) => {
-                    let normal = Vector::new(
-                        delaunay.points[i0].y - delaunay.points[i1].y,
-                        delaunay.points[i1].x - delaunay.points[i0].x,
-                    );
+impl<S: Float> Voronoi<S> {
+    // Wraps a graph read from elsewhere once its rays check out: each has a finite origin, a
+    // unit direction, a positive length and two distinct sites whose cells meet at its vertex.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_graph(graph: Graph<Ray<S>, S>) -> Result<Self, crate::ValidationError> {
+        graph.validate()?;
+
+        let tolerance = 64.0 * S::EPSILON.to_f64();
+        for vertex in graph.vertices() {
+            let ray = match vertex.position() {
+                Right(ray) => ray,
+                Left(_) => continue,
+            };
+            let (dx, dy) = (ray.direction.x.to_f64(), ray.direction.y.to_f64());
+            let length = ray.length.to_f64();
+            let finite = ray.origin.x.to_f64().is_finite()
+                && ray.origin.y.to_f64().is_finite()
+                && length.is_finite()
+                && length > 0.0
+                && (dx.hypot(dy) - 1.0).abs() <= tolerance;
+
+            let (a, b) = (ray.sites.0.index(), ray.sites.1.index());
+            let meets = |site| vertex.faces().any(|face| face.id().index() == site);
+            if !finite || a == b || !meets(a) || !meets(b) {
+                return Err(crate::ValidationError::BrokenRay(vertex.id()));
+            }
+        }
+
+        Ok(Voronoi(graph))
+    }
+
+    /// The name `"delaunay_edge"` is reserved for [`delaunay_edge`](Voronoi::delaunay_edge).
+    #[inline]
+    pub fn edge_attributes_mut(&mut self) -> &mut Attributes {
+        self.0.edge_attributes_mut()
+    }
+
+    #[inline]
+    pub fn face_attributes_mut(&mut self) -> &mut Attributes {
+        self.0.face_attributes_mut()
+    }
+
+    /// The name `"delaunay_face"` is reserved for [`delaunay_face`](Voronoi::delaunay_face).
+    #[inline]
+    pub fn vertex_attributes_mut(&mut self) -> &mut Attributes {
+        self.0.vertex_attributes_mut()
+    }
+
+    /// Site of the cell `face`.
+    #[inline]
+    pub fn delaunay_vertex(&self, face: FaceId) -> VertexId {
+        VertexId::new(face.index())
+    }
+
+    /// Delaunay triangle whose circumcenter is `vertex`. After `merge_coincident_vertices`
+    /// this is one of the triangles merged into it.
+    ///
+    /// Panics if a vertex attribute `"delaunay_face"` holds anything but `FaceId`s.
+    #[inline]
+    pub fn delaunay_face(&self, vertex: VertexId) -> FaceId {
+        match mapping::<FaceId>(&self.vertex_attributes, DELAUNAY_FACE) {
+            Some(faces) => faces[vertex.index()],
+            None => FaceId::new(vertex.index()),
+        }
+    }
+
+    /// Delaunay edge bisected by `edge`.
+    ///
+    /// Panics if an edge attribute `"delaunay_edge"` holds anything but `EdgeId`s.
+    #[inline]
+    pub fn delaunay_edge(&self, edge: EdgeId) -> EdgeId {
+        match mapping::<EdgeId>(&self.edge_attributes, DELAUNAY_EDGE) {
+            Some(edges) => edges[edge.index()],
+            None => edge,
+        }
+    }
+
+    /// Contracts Voronoi edges shorter than `tolerance`, which cocircular sites produce,
+    /// and returns how many were removed. Edges of triangular cells are kept. The Delaunay
+    /// ids of the remaining vertices and edges are kept in the `"delaunay_face"` vertex and
+    /// `"delaunay_edge"` edge attributes, which the mappings above read. Both names are
+    /// reserved: this panics if either holds a column of another type.
+    pub fn merge_coincident_vertices(&mut self, tolerance: S) -> usize {
+        if mapping::<FaceId>(&self.vertex_attributes, DELAUNAY_FACE).is_none() {
+            let faces = (0..self.vertex_count()).map(FaceId::new).collect();
+            self.0
+                .vertex_attributes
+                .insert(DELAUNAY_FACE, faces)
+                .unwrap();
+        }
+        if mapping::<EdgeId>(&self.edge_attributes, DELAUNAY_EDGE).is_none() {
+            let edges = (0..self.edge_count()).map(EdgeId::new).collect();
+            self.0.edge_attributes.insert(DELAUNAY_EDGE, edges).unwrap();
+        }
+
+        let tolerance = tolerance.to_f64();
+        let mut merged = 0;
+        let mut edge = 0;
 
-                    vertices.push(GraphVertex::new(e0, Right(normal)));
+        while edge < self.edges.len() {
+            let short = match (
+                self.position_of(self.origin_of(edge)),
+                self.position_of(self.origin_of(edge ^ 1)),
+            ) {
+                (Some(p0), Some(p1)) => {
+                    let dx = p1.x.to_f64() - p0.x.to_f64();
+                    let dy = p1.y.to_f64() - p0.y.to_f64();
+                    dx.hypot(dy) <= tolerance
                 }
-                _ => unsafe { unreachable_unchecked() },
+                _ => false,
+            };
+
+            if short && self.0.contract_edge(edge).is_ok() {
+                merged += 1;
+            } else {
+                edge += 2;
             }
-        });
+        }
+
+        #[cfg(feature = "validate")]
+        debug_assert_eq!(self.validate(), Ok(()));
+
+        merged
+    }
+}
+
+pub(crate) const DELAUNAY_FACE: &str = "delaunay_face";
+pub(crate) const DELAUNAY_EDGE: &str = "delaunay_edge";
 
-        Voronoi(Graph {
-            points,
-            edges,
-            faces,
-            vertices,
+// Id mapping stored under the reserved `name`, if merging added one. A column of that name
+// holding anything else would otherwise read as no mapping at all, so it panics instead.
+pub(crate) fn mapping<'a, A: Attribute>(attributes: &'a Attributes, name: &str) -> Option<&'a [A]> {
+    if !attributes.contains(name) {
+        return None;
+    }
+    match attributes.get::<A>(name) {
+        Some(values) => Some(values),
+        None => panic!(
+            "attribute `{}` is reserved for the Delaunay ids of a Voronoi diagram",
+            name
+        ),
+    }
+}
+
+// Circumcenter of a finite triangle, or its longest side when its corners are collinear.
+fn circumcenter<S: Float>(face: Face<'_, (), S>) -> Option<Either<Point<S>, Edge<'_, (), S>>> {
+    let positions = face
+        .vertices()
+        .map(|vertex| vertex.position().left())
+        .collect::<Option<Vec<_>>>()?;
+    let (p0, p1, p2) = (positions[0], positions[1], positions[2]);
+
+    if let Some(center) = Point::robust_circumcenter(&p0, &p1, &p2) {
+        return Some(Left(center));
+    }
+    face.edges()
+        .max_by(|a, b| {
+            a.length()
+                .partial_cmp(&b.length())
+                .unwrap_or(std::cmp::Ordering::Equal)
         })
+        .map(Right)
+}
+
+// Ray from `origin` across the hull edge `hull`, whose left face is infinite, or `None` if
+// either end of `hull` is infinite too.
+fn ray<S: Float>(hull: Edge<'_, (), S>, origin: Point<S>) -> Option<Ray<S>> {
+    let (v0, v1) = hull.vertices();
+    let (p0, p1) = (v0.position().left()?, v1.position().left()?);
+
+    let (dx, dy) = (p0.y - p1.y, p1.x - p0.x);
+    let length = (dx * dx + dy * dy).sqrt();
+    Some(Ray {
+        origin,
+        direction: Vector::new(dx / length, dy / length),
+        length,
+        sites: (v0.id(), v1.id()),
+    })
+}
+
+// Voronoi vertex dual to `face`: its circumcenter, or a ray for infinite and flat faces.
+// `None` if `face` does not sit in a triangulation with a closed hull.
+fn dual_vertex<S: Float>(face: Face<'_, (), S>) -> Option<Either<Point<S>, Ray<S>>> {
+    match circumcenter(face) {
+        Some(Left(center)) => Some(Left(center)),
+        // Flat triangles stack along collinear runs of the hull, each folded onto the
+        // next across its longest side, so their circumcenters all lie at infinity beyond it.
+        Some(Right(side)) => ray(side.twin(), side.midpoint()?).map(Right),
+        None => {
+            // The hull edge is the only one of an infinite face with two finite ends.
+            let hull = face.edges().find(|edge| !edge.is_infinite())?;
+            let origin = match circumcenter(hull.right_face())? {
+                Left(center) => center,
+                Right(side) => side.midpoint()?,
+            };
+            ray(hull, origin).map(Right)
+        }
+    }
+}
+
+/// Delaunay face and edge attributes carry over to the Voronoi vertices and edges, so a
+/// Delaunay face attribute `"delaunay_face"` or edge attribute `"delaunay_edge"` would pass for
+/// the id mappings of [`Voronoi::merge_coincident_vertices`]. Either one panics.
+impl<S: Float> From<&Delaunay<S>> for Voronoi<S> {
+    fn from(delaunay: &Delaunay<S>) -> Self {
+        for (attributes, name) in [
+            (delaunay.face_attributes(), DELAUNAY_FACE),
+            (delaunay.edge_attributes(), DELAUNAY_EDGE),
+        ] {
+            assert!(
+                !attributes.contains(name),
+                "attribute `{}` is reserved for the Delaunay ids of a Voronoi diagram",
+                name
+            );
+        }
+
+        // `Delaunay` is built by the triangulator or checked by `triangulation` when read, so
+        // every face has a dual vertex.
+        let voronoi =
+            Voronoi(delaunay.dual(|face| {
+                dual_vertex(face).expect("Delaunay graph is not a closed triangulation")
+            }));
+
+        #[cfg(feature = "validate")]
+        debug_assert_eq!(voronoi.validate(), Ok(()));
+
+        voronoi
     }
 }
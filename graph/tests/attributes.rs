@@ -0,0 +1,201 @@
+use graph::{Bounds, Delaunay, Edge, EdgeId, Face, FaceId, Point, Vertex, VertexId, Voronoi};
+
+mod common;
+
+// Names elements after their positions, so that a value can be checked against the element
+// holding it wherever edits move it.
+fn vertex_name(vertex: Vertex<'_, ()>) -> String {
+    match vertex.position().left() {
+        Some(p) => format!("({}, {})", p.x, p.y),
+        None => "infinite".to_owned(),
+    }
+}
+
+fn edge_name(edge: Edge<'_, ()>) -> String {
+    let mut ends = [vertex_name(edge.origin()), vertex_name(edge.destination())];
+    ends.sort();
+    ends.join(" ")
+}
+
+fn face_name(face: Face<'_, ()>) -> String {
+    let mut corners = face.vertices().map(vertex_name).collect::<Vec<_>>();
+    corners.sort();
+    corners.join(" ")
+}
+
+fn named() -> Delaunay {
+    let mut delaunay = common::delaunay(100);
+    let vertices = delaunay.vertices().map(vertex_name).collect();
+    let edges = delaunay.edges().map(edge_name).collect();
+    let faces = delaunay.faces().map(face_name).collect();
+    delaunay
+        .vertex_attributes_mut()
+        .insert("name", vertices)
+        .unwrap();
+    delaunay
+        .edge_attributes_mut()
+        .insert("name", edges)
+        .unwrap();
+    delaunay
+        .face_attributes_mut()
+        .insert("name", faces)
+        .unwrap();
+    delaunay
+}
+
+fn name(value: Option<&String>) -> &str {
+    value.unwrap().as_str()
+}
+
+#[test]
+fn columns() {
+    let mut delaunay = common::delaunay(100);
+    let count = delaunay.vertex_count();
+    let vertices = delaunay.vertex_attributes_mut();
+
+    assert_eq!(vertices.len(), count);
+    assert_eq!(
+        vertices
+            .insert("short", vec![0u32; count - 1])
+            .unwrap_err()
+            .len(),
+        count - 1
+    );
+    assert!(!vertices.contains("short"));
+
+    vertices.add::<f64>("weight")[3] = 2.0;
+    assert_eq!(vertices.get::<f64>("weight").unwrap()[3], 2.0);
+    assert!(vertices.get::<f32>("weight").is_none());
+    assert_eq!(vertices.names().collect::<Vec<_>>(), vec!["weight"]);
+
+    assert!(vertices.remove("weight"));
+    assert!(!vertices.remove("weight"));
+    assert!(delaunay
+        .vertex(VertexId::new(3))
+        .attribute::<f64>("weight")
+        .is_none());
+}
+
+#[test]
+fn split() {
+    let mut delaunay = named();
+    let face = delaunay.faces().find(|face| !face.is_infinite()).unwrap();
+    let (id, old) = (face.id(), face_name(face));
+    let corners = face.vertices().map(|v| v.position().left().unwrap());
+    let (x, y) = corners.fold((0.0, 0.0), |(x, y), p| (x + p.x / 3.0, y + p.y / 3.0));
+
+    let vertex = delaunay.split_face(id, Point::new(x, y)).unwrap();
+    assert_eq!(delaunay.validate(), Ok(()));
+
+    // The new vertex and its spokes start out empty, the three faces of the split copy the
+    // face they were cut from, and everything else keeps its value.
+    assert_eq!(name(delaunay.vertex(vertex).attribute("name")), "");
+    for face in delaunay.vertex(vertex).faces() {
+        assert_eq!(name(face.attribute("name")), old);
+    }
+    for v in delaunay.vertices().filter(|v| v.id() != vertex) {
+        assert_eq!(name(v.attribute("name")), vertex_name(v));
+    }
+    for edge in delaunay.edges() {
+        let expected = if edge.origin().id() == vertex || edge.destination().id() == vertex {
+            String::new()
+        } else {
+            edge_name(edge)
+        };
+        assert_eq!(name(edge.attribute("name")), expected);
+    }
+}
+
+#[test]
+fn collapse() {
+    let mut delaunay = named();
+    let vertices = delaunay.vertex_count();
+    let edge = (0..delaunay.edge_count())
+        .map(EdgeId::new)
+        .find(|&id| !delaunay.edge(id).is_infinite())
+        .unwrap();
+    let origin = delaunay.edge(edge).origin().position().left().unwrap();
+
+    // Removing a vertex, two faces and three edges swaps the last of each into their slots.
+    let (vertex, _) = delaunay.collapse_edge(edge, origin).unwrap();
+    assert_eq!(delaunay.validate(), Ok(()));
+    assert_eq!(delaunay.vertex_count(), vertices - 1);
+
+    for v in delaunay.vertices() {
+        assert_eq!(name(v.attribute("name")), vertex_name(v));
+    }
+    // Edges and faces around the merged vertex keep their values from before the collapse.
+    let touches =
+        |edge: &Edge<'_, ()>| edge.origin().id() == vertex || edge.destination().id() == vertex;
+    for edge in delaunay.edges().filter(|edge| !touches(edge)) {
+        assert_eq!(name(edge.attribute("name")), edge_name(edge));
+    }
+    let around = delaunay
+        .vertex(vertex)
+        .faces()
+        .map(|f| f.id())
+        .collect::<Vec<_>>();
+    for face in delaunay.faces().filter(|f| !around.contains(&f.id())) {
+        assert_eq!(name(face.attribute("name")), face_name(face));
+    }
+}
+
+#[test]
+fn dual_and_clip() {
+    let delaunay = named();
+    let voronoi = Voronoi::from(&delaunay);
+
+    // Sites name their cells and triangles their circumcenters; edges keep their names.
+    for cell in voronoi.faces() {
+        let site = delaunay.vertex(voronoi.delaunay_vertex(cell.id()));
+        assert_eq!(name(cell.attribute("name")), vertex_name(site));
+    }
+    for vertex in voronoi.vertices() {
+        let face = delaunay.face(FaceId::new(vertex.id().index()));
+        assert_eq!(name(vertex.attribute("name")), face_name(face));
+    }
+    for edge in voronoi.edges() {
+        let bisected = delaunay.edge(voronoi.delaunay_edge(edge.id()));
+        assert_eq!(name(edge.attribute("name")), edge_name(bisected));
+    }
+
+    // Clipping picks the attributes of the cell each piece was cut from.
+    let clipped = voronoi.clipped(&Bounds::unit()).unwrap();
+    let sites = clipped.face_attributes().get::<VertexId>("site").unwrap();
+    let mut pieces = 0;
+    for piece in clipped.faces().filter(|face| !face.is_infinite()) {
+        let site = delaunay.vertex(sites[piece.id().index()]);
+        assert_eq!(name(piece.attribute("name")), vertex_name(site));
+        pieces += 1;
+    }
+    assert_eq!(pieces, 100);
+}
+
+#[test]
+fn site_attribute_after_edits() {
+    let mut delaunay = common::delaunay(100);
+    let edge = delaunay
+        .edges()
+        .find(|edge| !edge.is_infinite() && edge.midpoint().is_some())
+        .unwrap();
+    let (id, midpoint) = (edge.id(), edge.midpoint().unwrap());
+    delaunay.collapse_edge(id, midpoint).unwrap();
+    let face = delaunay.faces().find(|face| !face.is_infinite()).unwrap();
+    let (id, centroid) = (face.id(), face.centroid().unwrap());
+    delaunay.split_face(id, centroid).unwrap();
+
+    // 100 points went in, one vertex went away and one came in.
+    let sites = delaunay.vertex_count() - 1;
+    assert_eq!(sites, 100);
+    let values = (0..sites + 1).map(|i| i as u32).collect::<Vec<_>>();
+    assert!(delaunay.insert_site_attribute("id", values).is_err());
+
+    let values = (1..=sites).map(|i| i as u32).collect::<Vec<_>>();
+    delaunay.insert_site_attribute("id", values).unwrap();
+    for vertex in delaunay.vertices() {
+        assert_eq!(
+            vertex.attribute::<u32>("id"),
+            Some(&(vertex.id().index() as u32))
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use graph::{Bounds, Delaunay, Graph, GraphView, Point, Voronoi};
+
+mod common;
+
+fn bytes() -> (Delaunay, Vec<u8>) {
+    let delaunay = common::delaunay(100);
+    let mut bytes = Vec::new();
+    delaunay.write_binary(&mut bytes).unwrap();
+    (delaunay, bytes)
+}
+
+// FNV-1a over little-endian 64-bit words, as the format checksums its contents.
+fn checksum(bytes: &mut [u8]) {
+    let end = bytes.len() - 8;
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for word in bytes[..end].chunks(8) {
+        let mut buffer = [0; 8];
+        buffer[..word.len()].copy_from_slice(word);
+        hash = (hash ^ u64::from_le_bytes(buffer)).wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    bytes[end..].copy_from_slice(&hash.to_le_bytes());
+}
+
+#[test]
+fn round_trip() {
+    let (delaunay, bytes) = bytes();
+    let view = GraphView::<f64>::new(&bytes).unwrap();
+    assert_eq!(view.edge_count(), delaunay.edge_count());
+    assert_eq!(view.face_count(), delaunay.face_count());
+
+    let graph = Graph::<(), f64>::read_binary(&bytes[..]).unwrap();
+    assert_eq!(graph.validate(), Ok(()));
+    for (read, written) in graph.vertices().zip(delaunay.vertices()) {
+        let coords = |point: Option<Point>| point.map(|point| (point.x, point.y));
+        assert_eq!(
+            coords(read.position().left()),
+            coords(written.position().left())
+        );
+        let read = read
+            .outgoing_edges()
+            .map(|edge| edge.id())
+            .collect::<Vec<_>>();
+        let written = written
+            .outgoing_edges()
+            .map(|edge| edge.id())
+            .collect::<Vec<_>>();
+        assert_eq!(read, written);
+    }
+}
+
+#[test]
+fn view_navigation() {
+    let (delaunay, bytes) = bytes();
+    let view = GraphView::<f64>::new(&bytes).unwrap();
+
+    for (read, written) in view.edges().zip(delaunay.edges()) {
+        for (read, written) in [(read, written), (read.twin(), written.twin())] {
+            assert_eq!(read.half_edge_id(), written.half_edge_id());
+            assert_eq!(read.id(), written.id());
+            assert_eq!(read.origin().id(), written.origin().id());
+            assert_eq!(read.destination().id(), written.destination().id());
+            assert_eq!(read.next().half_edge_id(), written.next().half_edge_id());
+            assert_eq!(read.prev().half_edge_id(), written.prev().half_edge_id());
+            assert_eq!(read.left_face().id(), written.left_face().id());
+            assert_eq!(read.right_face().id(), written.right_face().id());
+            assert_eq!(read.is_infinite(), written.is_infinite());
+        }
+    }
+
+    for (read, written) in view.faces().zip(delaunay.faces()) {
+        let edges = read.edges().map(|edge| edge.half_edge_id());
+        assert!(edges.eq(written.edges().map(|edge| edge.half_edge_id())));
+        let neighbors = read.neighbors().map(|face| face.id());
+        assert!(neighbors.eq(written.neighbors().map(|face| face.id())));
+        assert!(read
+            .vertices()
+            .map(|v| v.id())
+            .eq(view.face_vertices(read.id())));
+        assert_eq!(read.is_infinite(), written.is_infinite());
+    }
+
+    for (read, written) in view.vertices().zip(delaunay.vertices()) {
+        let coords = |point: Option<Point>| point.map(|point| (point.x, point.y));
+        assert_eq!(
+            coords(read.position().left()),
+            coords(written.position().left())
+        );
+        let incoming = read.incoming_edges().map(|edge| edge.half_edge_id());
+        assert!(incoming.eq(written.incoming_edges().map(|edge| edge.half_edge_id())));
+        let neighbors = read.neighbors().map(|vertex| vertex.id());
+        assert!(neighbors.eq(written.neighbors().map(|vertex| vertex.id())));
+        let faces = read.faces().map(|face| face.id());
+        assert!(faces.eq(written.faces().map(|face| face.id())));
+        assert_eq!(read.is_infinite(), written.is_infinite());
+    }
+
+    let face = delaunay.faces().nth(3).unwrap();
+    assert_eq!(view.face(face.id()).id(), face.id());
+    let edge = face.edges().next().unwrap();
+    assert_eq!(view.edge(edge.id()).id(), edge.id());
+    assert_eq!(
+        view.half_edge(edge.half_edge_id()).origin().id(),
+        edge.origin().id()
+    );
+    let vertex = edge.destination();
+    assert_eq!(view.vertex(vertex.id()).id(), vertex.id());
+}
+
+#[test]
+fn delaunay_round_trip() {
+    let (delaunay, bytes) = bytes();
+    let mut read = Delaunay::<f64>::read_binary(&bytes[..]).unwrap();
+    assert_eq!(read.verify(), Ok(()));
+    assert_eq!(
+        Voronoi::from(&read).vertex_count(),
+        Voronoi::from(&delaunay).vertex_count()
+    );
+
+    let face = read.faces().find(|face| !face.is_infinite()).unwrap();
+    let (id, centroid) = (face.id(), face.centroid().unwrap());
+    read.split_face_legalized(id, centroid).unwrap();
+    assert_eq!(read.verify(), Ok(()));
+}
+
+#[test]
+fn not_a_triangulation() {
+    let (delaunay, _) = bytes();
+    let clipped = Voronoi::from(&delaunay).clipped(&Bounds::unit()).unwrap();
+    let mut bytes = Vec::new();
+    clipped.write_binary(&mut bytes).unwrap();
+
+    assert!(Graph::<(), f64>::read_binary(&bytes[..]).is_ok());
+    let error = Delaunay::<f64>::read_binary(&bytes[..]).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn corrupted() {
+    let (_, mut bytes) = bytes();
+    let middle = bytes.len() / 2;
+    bytes[middle] ^= 1;
+    assert!(GraphView::<f64>::new(&bytes).is_err());
+    assert!(Graph::<(), f64>::read_binary(&bytes[..]).is_err());
+    assert!(Delaunay::<f64>::read_binary(&bytes[..]).is_err());
+}
+
+#[test]
+fn broken_cycle() {
+    // Half-edge 0 made its own successor, under a valid checksum.
+    let (_, mut bytes) = bytes();
+    let next = 40 + 16 * 100 + 4;
+    bytes[next..next + 4].copy_from_slice(&1u32.to_le_bytes());
+    checksum(&mut bytes);
+    match GraphView::<f64>::new(&bytes) {
+        Ok(_) => panic!("broken cycle accepted"),
+        Err(error) => assert_ne!(error.to_string(), "checksum mismatch"),
+    }
+}
+
+// Rewrites a file with 4-byte indices to 8-byte ones, keeping the infinite marker.
+fn widen(bytes: &[u8]) -> Vec<u8> {
+    let count = |i: usize| {
+        let mut buffer = [0; 8];
+        buffer.copy_from_slice(&bytes[8 + 8 * i..16 + 8 * i]);
+        u64::from_le_bytes(buffer) as usize
+    };
+    let (points, edges, faces, vertices) = (count(0), count(1), count(2), count(3));
+    let start = 40 + points * 16;
+    let end = start + 4 * (3 * edges + faces + 2 * vertices);
+
+    let mut wide = bytes[..start].to_vec();
+    wide[7] = 8;
+    for index in bytes[start..end].chunks(4) {
+        let index = u32::from_le_bytes([index[0], index[1], index[2], index[3]]);
+        let index = if index == u32::MAX {
+            u64::MAX
+        } else {
+            u64::from(index)
+        };
+        wide.extend_from_slice(&index.to_le_bytes());
+    }
+    wide.extend_from_slice(&[0; 8]);
+    checksum(&mut wide);
+    wide
+}
+
+#[test]
+fn wide_indices() {
+    let (delaunay, bytes) = bytes();
+    let mut wide = widen(&bytes);
+    let view = GraphView::<f64>::new(&wide).unwrap();
+    assert_eq!(view.edge_count(), delaunay.edge_count());
+
+    // The `next` of half-edge 0 beyond what a compact index holds.
+    let next = 40 + 16 * 100 + 8;
+    wide[next..next + 8].copy_from_slice(&(1u64 << 33).to_le_bytes());
+    checksum(&mut wide);
+    match GraphView::<f64>::new(&wide) {
+        Ok(_) => panic!("out of range index accepted"),
+        Err(error) => assert_ne!(error.to_string(), "checksum mismatch"),
+    }
+    assert!(Graph::<(), f64>::read_binary(&wide[..]).is_err());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mapped() {
+    let (delaunay, mut bytes) = bytes();
+    let path = std::env::temp_dir().join(format!("graph-{}.trig", std::process::id()));
+
+    std::fs::write(&path, &bytes).unwrap();
+    let mapped = graph::MappedGraph::<f64>::open(&path).unwrap();
+    assert_eq!(mapped.view().face_count(), delaunay.face_count());
+    drop(mapped);
+
+    bytes[40 + 16 * 100 + 4..40 + 16 * 100 + 8].copy_from_slice(&1u32.to_le_bytes());
+    checksum(&mut bytes);
+    std::fs::write(&path, &bytes).unwrap();
+    assert!(graph::MappedGraph::<f64>::open(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,118 @@
+use graph::{Cell, Delaunay, Point, VertexId, Voronoi};
+
+mod common;
+
+use common::close_to;
+
+fn site(delaunay: &Delaunay, x: f64, y: f64) -> VertexId {
+    delaunay
+        .vertices()
+        .find(|vertex| vertex.position().left().is_some_and(|p| close_to(p, x, y)))
+        .unwrap()
+        .id()
+}
+
+#[test]
+fn diamond_around_center() {
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(0.0, 1.0),
+        Point::new(-1.0, 0.0),
+        Point::new(0.0, -1.0),
+    ];
+    let delaunay: Delaunay = Delaunay::from(points).unwrap();
+    let voronoi = Voronoi::from(&delaunay);
+
+    let center = site(&delaunay, 0.0, 0.0);
+    let cell = voronoi.cell(center).unwrap();
+    assert!(cell.is_bounded());
+    assert_eq!(cell.points().len(), 4);
+    assert!((cell.area().unwrap() - 1.0).abs() < 1e-12);
+    assert!((cell.perimeter().unwrap() - 4.0).abs() < 1e-12);
+    assert!(close_to(cell.centroid().unwrap(), 0.0, 0.0));
+
+    let mut neighbors = voronoi.neighbor_sites(center).collect::<Vec<_>>();
+    neighbors.sort();
+    let mut outer = [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)]
+        .iter()
+        .map(|&(x, y)| site(&delaunay, x, y))
+        .collect::<Vec<_>>();
+    outer.sort();
+    assert_eq!(neighbors, outer);
+
+    let right = site(&delaunay, 1.0, 0.0);
+    match voronoi.cell(right).unwrap() {
+        Cell::Unbounded { points, rays } => {
+            // Counter-clockwise around (1, 0), from the upper ray to the lower one.
+            assert_eq!(points.len(), 2);
+            assert!(close_to(points[0], 0.5, 0.5));
+            assert!(close_to(points[1], 0.5, -0.5));
+
+            let h = 0.5f64.sqrt();
+            let (first, last) = (rays.0.direction(), rays.1.direction());
+            assert!(close_to(rays.0.origin(), 0.5, 0.5));
+            assert!((first.x - h).abs() < 1e-12 && (first.y - h).abs() < 1e-12);
+            assert!(close_to(rays.1.origin(), 0.5, -0.5));
+            assert!((last.x - h).abs() < 1e-12 && (last.y + h).abs() < 1e-12);
+        }
+        cell => panic!("bounded hull cell {:?}", cell),
+    }
+    assert_eq!(voronoi.neighbor_sites(right).count(), 3);
+    assert!(voronoi.cell(VertexId::new(0)).is_none());
+}
+
+#[test]
+fn grid() {
+    let n = 5;
+    let points = (0..n * n)
+        .map(|i| Point::new((i % n) as f64, (i / n) as f64))
+        .collect();
+    let delaunay: Delaunay = Delaunay::from(points).unwrap();
+    let voronoi = Voronoi::from(&delaunay);
+
+    let mut bounded = 0;
+    for (site, cell) in voronoi.cells() {
+        let p = delaunay.vertex(site).position().left().unwrap();
+        let inner = p.x > 0.0 && p.x < 4.0 && p.y > 0.0 && p.y < 4.0;
+        assert_eq!(cell.is_bounded(), inner, "{:?}", p);
+        if inner {
+            bounded += 1;
+            assert!((cell.area().unwrap() - 1.0).abs() < 1e-12);
+            assert!((cell.perimeter().unwrap() - 4.0).abs() < 1e-12);
+            assert!(close_to(cell.centroid().unwrap(), p.x, p.y));
+        } else {
+            assert_eq!(cell.area(), None);
+            assert!(cell.centroid().is_none());
+        }
+    }
+    assert_eq!(bounded, 9);
+}
+
+#[test]
+fn collinear_and_one() {
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(3.0, 0.0),
+        Point::new(4.0, 0.0),
+        Point::new(2.0, 1.0),
+    ];
+    let delaunay: Delaunay = Delaunay::from(points).unwrap();
+    let voronoi = Voronoi::from(&delaunay);
+
+    assert_eq!(voronoi.cells().count(), 6);
+    assert!(voronoi.cells().all(|(_, cell)| !cell.is_bounded()));
+
+    let middle = site(&delaunay, 2.0, 0.0);
+    let mut neighbors = voronoi.neighbor_sites(middle).collect::<Vec<_>>();
+    neighbors.sort();
+    let mut expected = vec![
+        site(&delaunay, 1.0, 0.0),
+        site(&delaunay, 3.0, 0.0),
+        site(&delaunay, 2.0, 1.0),
+    ];
+    expected.sort();
+    assert_eq!(neighbors, expected);
+}
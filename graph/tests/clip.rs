@@ -0,0 +1,173 @@
+use graph::distributions::{Circle, Sobol};
+use graph::{Bounds, Delaunay, Point, VertexId, Voronoi};
+use rand::distributions::Distribution;
+use rand::{rngs::StdRng, SeedableRng};
+
+// Clips the cells of `points` to the unit square and checks that every site keeps one piece,
+// that cells lying inside the square keep their area and that the pieces tile the square.
+fn check_clipped(points: Vec<Point>) {
+    let count = points.len();
+    let delaunay = Delaunay::from(points).unwrap();
+    let voronoi = Voronoi::from(&delaunay);
+    let clipped = voronoi.clipped(&Bounds::unit()).unwrap();
+
+    let sites = clipped.face_attributes().get::<VertexId>("site").unwrap();
+    let mut seen = vec![false; delaunay.vertex_count()];
+    let mut total = 0.0;
+    for face in clipped.faces() {
+        let area = match face.area() {
+            Some(area) => area,
+            None => continue,
+        };
+        let site = sites[face.id().index()];
+        assert!(!seen[site.index()], "{:?} clipped twice", site);
+        seen[site.index()] = true;
+        total += area;
+
+        let cell = voronoi.cell(site).unwrap();
+        let inside = cell
+            .points()
+            .iter()
+            .all(|p| p.x > 0.0 && p.x < 1.0 && p.y > 0.0 && p.y < 1.0);
+        if let (true, Some(expected)) = (inside, cell.area()) {
+            assert!((area - expected).abs() < 1e-9, "{:?}", site);
+        }
+    }
+
+    assert_eq!(seen.iter().filter(|&&seen| seen).count(), count);
+    assert!((total - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn three_sites() {
+    let points = vec![
+        Point::new(0.9, 0.9),
+        Point::new(0.9, 0.1),
+        Point::new(0.1, 0.9),
+    ];
+    let delaunay: Delaunay = Delaunay::from(points).unwrap();
+    let clipped = Voronoi::from(&delaunay).clipped(&Bounds::unit()).unwrap();
+    let sites = clipped.face_attributes().get::<VertexId>("site").unwrap();
+
+    let mut areas = clipped
+        .faces()
+        .filter_map(|face| {
+            let area = face.area()?;
+            let site = delaunay.vertex(sites[face.id().index()]);
+            let p = site.position().left().unwrap();
+            Some(((p.x, p.y), area))
+        })
+        .collect::<Vec<_>>();
+    areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let expected = [((0.1, 0.9), 0.375), ((0.9, 0.1), 0.375), ((0.9, 0.9), 0.25)];
+    assert_eq!(areas.len(), expected.len());
+    for (&(site, area), &(expected_site, expected_area)) in areas.iter().zip(&expected) {
+        assert_eq!(site, expected_site);
+        assert!((area - expected_area).abs() < 1e-9, "{:?}", site);
+    }
+}
+
+#[test]
+fn sobol() {
+    for &n in &[100, 256, 1000, 2000] {
+        check_clipped(Sobol::new().take(n).collect());
+    }
+}
+
+#[test]
+fn circle() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let points = Circle::new(Point::new(0.5, 0.5), 0.5)
+        .sample_iter(&mut rng)
+        .take(2000)
+        .collect();
+    check_clipped(points);
+}
+
+#[test]
+fn merged() {
+    let points = Sobol::new().take(1000).collect();
+    let delaunay = Delaunay::from(points).unwrap();
+    let mut voronoi = Voronoi::from(&delaunay);
+    voronoi.merge_coincident_vertices(1e-9);
+    assert!(voronoi.clipped(&Bounds::unit()).is_ok());
+}
+
+#[test]
+fn grid() {
+    for &n in &[4, 10, 20, 40] {
+        let points = (0..n * n)
+            .map(|i| {
+                Point::new(
+                    ((i % n) as f64 + 0.5) / n as f64,
+                    ((i / n) as f64 + 0.5) / n as f64,
+                )
+            })
+            .collect();
+        let delaunay = Delaunay::from(points).unwrap();
+        let voronoi = Voronoi::from(&delaunay);
+        let bounded = voronoi
+            .cells()
+            .filter(|(_, cell)| cell.is_bounded())
+            .count();
+        assert_eq!(bounded, (n - 2) * (n - 2), "{} by {}", n, n);
+
+        let clipped = voronoi.clipped(&Bounds::unit()).unwrap();
+        let areas = clipped
+            .faces()
+            .filter_map(|face| face.area())
+            .collect::<Vec<_>>();
+        assert_eq!(areas.len(), n * n, "{} by {}", n, n);
+        for area in areas {
+            assert!((area * (n * n) as f64 - 1.0).abs() < 1e-9, "{} by {}", n, n);
+        }
+    }
+}
+
+#[test]
+fn concave() {
+    // A U whose arms both reach above the bisector of the lower two sites, cutting the upper
+    // cell in two. The third site lies far enough to the right to miss the bounds.
+    let bounds = Bounds::Polygon(vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(1.0, 1.0),
+        Point::new(0.7, 1.0),
+        Point::new(0.7, 0.3),
+        Point::new(0.3, 0.3),
+        Point::new(0.3, 1.0),
+        Point::new(0.0, 1.0),
+    ]);
+    let points = vec![
+        Point::new(0.5, 0.1),
+        Point::new(0.5, 0.9),
+        Point::new(2.0, 0.5),
+    ];
+    let delaunay = Delaunay::from(points).unwrap();
+    let clipped = Voronoi::from(&delaunay).clipped(&bounds).unwrap();
+    assert_eq!(clipped.validate(), Ok(()));
+
+    let sites = clipped.face_attributes().get::<VertexId>("site").unwrap();
+    let mut areas = clipped
+        .faces()
+        .filter_map(|face| {
+            let site = delaunay.vertex(sites[face.id().index()]);
+            Some((site.position().left()?.y, face.area()?))
+        })
+        .collect::<Vec<_>>();
+    areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let expected = [(0.1, 0.42), (0.9, 0.15), (0.9, 0.15f64)];
+    assert_eq!(areas.len(), expected.len());
+    for (&(y, area), &(expected_y, expected_area)) in areas.iter().zip(&expected) {
+        assert_eq!(y, expected_y);
+        assert!((area - expected_area).abs() < 1e-9, "{:?}", y);
+    }
+
+    let delaunay = Delaunay::from(Sobol::new().take(500).collect()).unwrap();
+    let clipped = Voronoi::from(&delaunay).clipped(&bounds).unwrap();
+    assert_eq!(clipped.validate(), Ok(()));
+    let total = clipped.faces().filter_map(|face| face.area()).sum::<f64>();
+    assert!((total - 0.72).abs() < 1e-9);
+}
@@ -0,0 +1,103 @@
+use graph::cloud::{PointCloud, PointReader};
+use graph::Point;
+use std::fmt::Write;
+
+mod common;
+
+fn coordinates(points: &[Point]) -> Vec<(f64, f64)> {
+    points.iter().map(|p| (p.x, p.y)).collect()
+}
+
+#[test]
+fn csv_round_trip() {
+    let points = common::points(20);
+    let mut csv = String::from("id,\"lat\",lon,height\n");
+    for (i, p) in points.iter().enumerate() {
+        writeln!(csv, "{},{},{},{}", i, p.y, p.x, 2.0 * i as f64).unwrap();
+    }
+
+    let cloud = PointReader::csv()
+        .with_columns("lon", "lat")
+        .read::<_, f64>(csv.as_bytes())
+        .unwrap();
+
+    assert_eq!(coordinates(&cloud.points), coordinates(&points));
+    assert_eq!(cloud.columns.len(), 2);
+    assert_eq!(cloud.columns[0].0, "id");
+    assert_eq!(
+        cloud.columns[0].1,
+        (0..20).map(f64::from).collect::<Vec<_>>()
+    );
+    assert_eq!(cloud.columns[1].0, "height");
+    assert_eq!(cloud.columns[1].1[19], 38.0);
+}
+
+#[test]
+fn xyz_round_trip() {
+    let points = common::points(20);
+    let mut xyz = String::from("# exported points\n\n");
+    for (i, p) in points.iter().enumerate() {
+        writeln!(xyz, "{}\t{}  {} # point {}", p.x, p.y, -(i as f64), i).unwrap();
+    }
+
+    let cloud = PointReader::xyz().read::<_, f64>(xyz.as_bytes()).unwrap();
+
+    assert_eq!(coordinates(&cloud.points), coordinates(&points));
+    assert_eq!(cloud.columns.len(), 1);
+    assert_eq!(cloud.columns[0].0, "z");
+    assert_eq!(cloud.columns[0].1[5], -5.0);
+
+    let delaunay = cloud.delaunay().unwrap();
+    assert_eq!(delaunay.validate(), Ok(()));
+    for vertex in delaunay.vertices().skip(1) {
+        let position = vertex.position().left().unwrap();
+        let i = points
+            .iter()
+            .position(|p| (p.x, p.y) == (position.x, position.y))
+            .unwrap();
+        assert_eq!(vertex.attribute::<f64>("z"), Some(&-(i as f64)));
+    }
+}
+
+#[test]
+fn ragged_lines_are_rejected() {
+    let xyz = "0 0 1\n1 0\n";
+    let error = PointReader::xyz()
+        .read::<_, f64>(xyz.as_bytes())
+        .unwrap_err();
+    assert!(error.to_string().starts_with("line 2:"));
+}
+
+#[test]
+fn quoted_fields() {
+    let csv = "name,x,y\n\"a, \"\"b\"\"\",1,2\n  \"c\" , 3 ,4\n";
+    let cloud = PointReader::csv()
+        .with_columns("x", "y")
+        .read::<_, f64>(csv.as_bytes());
+    // The name column is not a number, but it must not shift x and y.
+    let error = cloud.unwrap_err().to_string();
+    assert!(error.contains("invalid number `a, \"b\"`"), "{}", error);
+
+    let csv = "x,\"y, north\"\n1,2\n\"3\",\"4\"\n";
+    let cloud = PointReader::csv()
+        .with_columns("x", "y, north")
+        .read::<_, f64>(csv.as_bytes())
+        .unwrap();
+    assert_eq!(coordinates(&cloud.points), vec![(1.0, 2.0), (3.0, 4.0)]);
+
+    for csv in ["x,y\n\"1,2\n", "x,y\n\"1\"0,2\n"] {
+        let error = PointReader::csv()
+            .read::<_, f64>(csv.as_bytes())
+            .unwrap_err();
+        assert!(error.to_string().starts_with("line 2:"), "{}", error);
+    }
+}
+
+#[test]
+fn short_column() {
+    let cloud = PointCloud {
+        points: common::points(20),
+        columns: vec![("z".to_owned(), vec![0.0; 19])],
+    };
+    assert!(cloud.delaunay().is_err());
+}
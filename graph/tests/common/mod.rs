@@ -0,0 +1,27 @@
+// Fixtures shared by the integration tests. Each test file compiles its own copy and uses
+// only part of it.
+#![allow(dead_code)]
+
+use graph::{Delaunay, Point};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// `count` points drawn uniformly from the unit square, the same ones on every call.
+pub fn points(count: usize) -> Vec<Point> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..count)
+        .map(|_| Point::new(rng.gen(), rng.gen()))
+        .collect()
+}
+
+/// Triangulation of `points(count)`.
+pub fn delaunay(count: usize) -> Delaunay {
+    Delaunay::from(points(count)).unwrap()
+}
+
+pub fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-12
+}
+
+pub fn close_to(point: Point, x: f64, y: f64) -> bool {
+    close(point.x, x) && close(point.y, y)
+}
@@ -0,0 +1,31 @@
+use graph::distributions::Halton;
+use graph::{Delaunay, Point};
+
+#[test]
+fn memory_usage() {
+    let points: Vec<Point> = Halton::new().take(1000).collect();
+    let delaunay = Delaunay::from(points).unwrap();
+
+    // About six half-edges of three indices, two faces of one index and a vertex of two per
+    // point, next to the 16 bytes of the point itself: 108 bytes with 32-bit indices and 200
+    // with 64-bit ones.
+    let per_point = delaunay.memory_usage() / 1000;
+    #[cfg(feature = "compact-index")]
+    assert!((108..120).contains(&per_point), "{}", per_point);
+    #[cfg(not(feature = "compact-index"))]
+    assert!((200..220).contains(&per_point), "{}", per_point);
+}
+
+#[test]
+fn max_points() {
+    #[cfg(feature = "compact-index")]
+    assert_eq!(Delaunay::<f64>::max_points(), 715_827_881);
+    #[cfg(not(feature = "compact-index"))]
+    assert_eq!(Delaunay::<f64>::max_points(), usize::MAX / 6 - 1);
+
+    // The infinite vertex and six half-edges per point just fit.
+    let max = Delaunay::<f64>::max_points();
+    assert!((max + 1).checked_mul(6).is_some());
+    #[cfg(feature = "compact-index")]
+    assert!((max + 1) * 6 <= u32::MAX as usize && (max + 2) * 6 > u32::MAX as usize);
+}
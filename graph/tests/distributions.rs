@@ -0,0 +1,93 @@
+use graph::distributions::{Halton, JitteredGrid, Sobol};
+use graph::Point;
+use rand::distributions::Distribution;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn coordinates(points: impl IntoIterator<Item = Point>) -> Vec<(f64, f64)> {
+    points.into_iter().map(|p| (p.x, p.y)).collect()
+}
+
+fn assert_near(points: impl IntoIterator<Item = Point>, expected: &[(f64, f64)]) {
+    let points = coordinates(points);
+    assert_eq!(points.len(), expected.len());
+    for (&(x, y), &(ex, ey)) in points.iter().zip(expected) {
+        assert!(
+            (x - ex).abs() < 1e-12 && (y - ey).abs() < 1e-12,
+            "{:?}",
+            points
+        );
+    }
+}
+
+#[test]
+fn halton() {
+    let expected = vec![
+        (0.5, 1.0 / 3.0),
+        (0.25, 2.0 / 3.0),
+        (0.75, 1.0 / 9.0),
+        (0.125, 4.0 / 9.0),
+        (0.625, 7.0 / 9.0),
+    ];
+    assert_near(Halton::new().take(5), &expected);
+    assert_near(
+        [Halton::point(0), Halton::point(3)],
+        &[(0.0, 0.0), expected[2]],
+    );
+}
+
+#[test]
+fn sobol() {
+    let expected = vec![
+        (0.5, 0.5),
+        (0.25, 0.75),
+        (0.75, 0.25),
+        (0.125, 0.625),
+        (0.625, 0.125),
+    ];
+    assert_eq!(coordinates(Sobol::new().take(5)), expected);
+
+    // Every power-of-two prefix after the origin puts one point in each row of as many rows.
+    let mut rows = Sobol::<f64>::new()
+        .take(63)
+        .map(|p| (p.y * 64.0) as usize)
+        .collect::<Vec<_>>();
+    rows.push(0);
+    rows.sort_unstable();
+    assert_eq!(rows, (0..64).collect::<Vec<_>>());
+}
+
+#[test]
+fn jittered_grid() {
+    let grid = JitteredGrid::new(4, 3, 1.0);
+    assert_eq!(grid.cell_count(), 12);
+
+    // Points walk the cells row by row and stay in them.
+    let mut rng = StdRng::seed_from_u64(0);
+    let points = grid.points::<f64, _>(&mut rng).collect::<Vec<_>>();
+    assert_eq!(points.len(), 12);
+    for (index, point) in points.iter().enumerate() {
+        let (column, row) = (index % 4, index / 4);
+        assert!(point.x >= column as f64 / 4.0 && point.x <= (column + 1) as f64 / 4.0);
+        assert!(point.y >= row as f64 / 3.0 && point.y <= (row + 1) as f64 / 3.0);
+    }
+
+    // Samples pick cells at random, the same ones for the same seed and in any thread.
+    let sample = |seed| {
+        let rng = StdRng::seed_from_u64(seed);
+        coordinates((&grid).sample_iter(rng).take(600).collect::<Vec<Point>>())
+    };
+    let sampled = std::thread::scope(|scope| scope.spawn(|| sample(1)).join().unwrap());
+    assert_eq!(sampled, sample(1));
+    let mut counts = [0; 12];
+    for (x, y) in sampled {
+        counts[(y * 3.0) as usize * 4 + (x * 4.0) as usize] += 1;
+    }
+    assert!(counts.iter().all(|&count| count > 25), "{:?}", counts);
+
+    let flat = JitteredGrid::new(2, 2, 0.0);
+    let points = flat.points::<f64, _>(&mut rng);
+    assert_eq!(
+        coordinates(points),
+        vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)]
+    );
+}
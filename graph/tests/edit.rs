@@ -0,0 +1,275 @@
+use graph::{Bounds, Delaunay, EdgeId, EditError, FaceId, Point, VertexId, Voronoi};
+use std::convert::TryInto;
+
+mod common;
+
+#[test]
+fn flips_keep_orientation() {
+    let count = common::delaunay(100).edge_count();
+    let mut refused = 0;
+    for edge in 0..count {
+        let mut delaunay = common::delaunay(100);
+        match delaunay.flip_edge(EdgeId::new(edge)) {
+            Ok(()) => assert_eq!(delaunay.validate(), Ok(()), "edge {}", edge),
+            Err(_) => refused += 1,
+        }
+    }
+    assert!(refused > 0 && refused < count);
+}
+
+#[test]
+fn flips_keep_the_hull() {
+    // A triangle around one interior site: flipping a hull edge would join the site to the
+    // infinite vertex and dent the hull.
+    let mut delaunay = Delaunay::from(vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(0.5, 1.0),
+        Point::new(0.5, 0.3),
+    ])
+    .unwrap();
+    let edges = delaunay.edges().map(|edge| edge.id()).collect::<Vec<_>>();
+    let hull = delaunay
+        .edges()
+        .filter(|edge| !edge.is_infinite())
+        .filter(|edge| edge.left_face().is_infinite() || edge.right_face().is_infinite())
+        .map(|edge| edge.id())
+        .collect::<Vec<_>>();
+    assert_eq!(hull.len(), 3);
+
+    for edge in edges {
+        let result = delaunay.flip_edge(edge);
+        if hull.contains(&edge) || delaunay.edge(edge).is_infinite() {
+            assert_eq!(result, Err(EditError::InfiniteVertex), "{:?}", edge);
+        }
+    }
+    assert_eq!(delaunay.validate(), Ok(()));
+    assert!(Voronoi::from(&delaunay).clipped(&Bounds::unit()).is_ok());
+}
+
+#[test]
+fn collapses_keep_orientation() {
+    let count = common::delaunay(100).edge_count();
+    let (mut degenerate, mut collapsed) = (0, 0);
+    for edge in 0..count {
+        let mut delaunay = common::delaunay(100);
+        let (v0, v1) = delaunay.edge(EdgeId::new(edge)).vertices();
+        let (p0, p1) = match (v0.position().left(), v1.position().left()) {
+            (Some(p0), Some(p1)) => (p0, p1),
+            _ => continue,
+        };
+
+        // Well beyond the destination, which folds some of the triangles around it.
+        let point = Point::new(p1.x + 2.0 * (p1.x - p0.x), p1.y + 2.0 * (p1.y - p0.y));
+        match delaunay.collapse_edge(EdgeId::new(edge), point) {
+            Ok(_) => {
+                assert_eq!(delaunay.validate(), Ok(()), "edge {}", edge);
+                collapsed += 1;
+            }
+            Err(EditError::Degenerate) => degenerate += 1,
+            Err(_) => {}
+        }
+    }
+    assert!(degenerate > 0 && collapsed > 0);
+}
+
+// Number of points stored, read from the header of the binary format.
+fn point_count(delaunay: &Delaunay) -> u64 {
+    let mut bytes = Vec::new();
+    delaunay.write_binary(&mut bytes).unwrap();
+    u64::from_le_bytes(bytes[8..16].try_into().unwrap())
+}
+
+#[test]
+fn collapses_renumber() {
+    let count = common::delaunay(100).edge_count();
+    let mut collapsed = 0;
+    for edge in (0..count).map(EdgeId::new) {
+        let mut delaunay = common::delaunay(100);
+        let midpoint = match delaunay.edge(edge).midpoint() {
+            Some(midpoint) => midpoint,
+            None => continue,
+        };
+
+        // Tag every element with its id before the collapse.
+        let (edges, faces, vertices) = (
+            delaunay.edges().map(|edge| edge.id()).collect::<Vec<_>>(),
+            delaunay.faces().map(|face| face.id()).collect::<Vec<_>>(),
+            delaunay
+                .vertices()
+                .map(|vertex| vertex.id())
+                .collect::<Vec<_>>(),
+        );
+        delaunay
+            .edge_attributes_mut()
+            .insert("id", edges.clone())
+            .unwrap();
+        delaunay
+            .face_attributes_mut()
+            .insert("id", faces.clone())
+            .unwrap();
+        delaunay
+            .vertex_attributes_mut()
+            .insert("id", vertices.clone())
+            .unwrap();
+        let points = point_count(&delaunay);
+        let origin = delaunay.edge(edge).origin().id();
+
+        let (vertex, renumbering) = match delaunay.collapse_edge(edge, midpoint) {
+            Ok(collapse) => collapse,
+            Err(_) => continue,
+        };
+        collapsed += 1;
+        assert_eq!(renumbering.vertex(origin), Some(vertex));
+        assert_eq!(point_count(&delaunay), points - 1);
+
+        for edge in delaunay.edges() {
+            let old = *edge.attribute::<EdgeId>("id").unwrap();
+            assert_eq!(renumbering.edge(old), Some(edge.id()));
+        }
+        for face in delaunay.faces() {
+            let old = *face.attribute::<FaceId>("id").unwrap();
+            assert_eq!(renumbering.face(old), Some(face.id()));
+        }
+        for vertex in delaunay.vertices() {
+            let old = *vertex.attribute::<VertexId>("id").unwrap();
+            assert_eq!(renumbering.vertex(old), Some(vertex.id()));
+        }
+
+        let removed = |ids: Vec<Option<()>>| ids.iter().filter(|id| id.is_none()).count();
+        let edges = edges.into_iter().map(|id| renumbering.edge(id).map(drop));
+        let faces = faces.into_iter().map(|id| renumbering.face(id).map(drop));
+        let vertices = vertices
+            .into_iter()
+            .map(|id| renumbering.vertex(id).map(drop));
+        assert_eq!(removed(edges.collect()), 3);
+        assert_eq!(removed(faces.collect()), 2);
+        assert_eq!(removed(vertices.collect()), 1);
+    }
+    assert!(collapsed > 0);
+}
+
+#[test]
+fn split_rejects_infinite_edges() {
+    let mut delaunay = common::delaunay(100);
+    let edge = delaunay
+        .edges()
+        .find(|edge| edge.is_infinite())
+        .unwrap()
+        .id();
+    assert_eq!(
+        delaunay.split_edge(edge, Point::new(2.0, 2.0)),
+        Err(EditError::InfiniteVertex)
+    );
+}
+
+#[test]
+fn split_faces_and_legalize() {
+    let count = common::delaunay(100).face_count();
+    let mut illegal = 0;
+    for face in (0..count).map(FaceId::new) {
+        let mut unlegalized = common::delaunay(100);
+        let mut delaunay = common::delaunay(100);
+        let centroid = match delaunay.face(face).centroid() {
+            Some(centroid) => centroid,
+            None => {
+                let point = Point::new(0.5, 0.5);
+                assert_eq!(
+                    delaunay.split_face(face, point),
+                    Err(EditError::InfiniteVertex)
+                );
+                continue;
+            }
+        };
+
+        let vertex = unlegalized.split_face(face, centroid).unwrap();
+        assert_eq!(unlegalized.validate(), Ok(()), "{:?}", face);
+        assert_eq!(unlegalized.vertex(vertex).outgoing_edges().count(), 3);
+        if unlegalized.verify().is_err() {
+            illegal += 1;
+        }
+
+        delaunay.split_face_legalized(face, centroid).unwrap();
+        assert_eq!(delaunay.validate(), Ok(()), "{:?}", face);
+        assert_eq!(delaunay.verify(), Ok(()), "{:?}", face);
+    }
+    assert!(illegal > 0);
+}
+
+#[test]
+fn split_edges_and_legalize() {
+    let count = common::delaunay(100).edge_count();
+    for edge in (0..count).map(EdgeId::new) {
+        let mut delaunay = common::delaunay(100);
+        let midpoint = match delaunay.edge(edge).midpoint() {
+            Some(midpoint) => midpoint,
+            None => continue,
+        };
+
+        delaunay.split_edge_legalized(edge, midpoint).unwrap();
+        assert_eq!(delaunay.validate(), Ok(()), "{:?}", edge);
+        assert_eq!(delaunay.verify(), Ok(()), "{:?}", edge);
+    }
+}
+
+#[test]
+fn collapse_edges_and_legalize() {
+    let count = common::delaunay(100).edge_count();
+    let (mut illegal, mut collapsed) = (0, 0);
+    for edge in (0..count).map(EdgeId::new) {
+        let mut unlegalized = common::delaunay(100);
+        let mut delaunay = common::delaunay(100);
+        let midpoint = match delaunay.edge(edge).midpoint() {
+            Some(midpoint) => midpoint,
+            None => continue,
+        };
+
+        if unlegalized.collapse_edge(edge, midpoint).is_err() {
+            continue;
+        }
+        if unlegalized.verify().is_err() {
+            illegal += 1;
+        }
+
+        delaunay.collapse_edge_legalized(edge, midpoint).unwrap();
+        assert_eq!(delaunay.validate(), Ok(()), "{:?}", edge);
+        assert!(delaunay.verify().is_ok(), "{:?}", edge);
+        collapsed += 1;
+    }
+    assert!(illegal > 0 && collapsed > 0);
+}
+
+#[test]
+fn splits_reject_points_outside() {
+    let mut delaunay = common::delaunay(100);
+    let (vertices, edges, faces) = (
+        delaunay.vertex_count(),
+        delaunay.edge_count(),
+        delaunay.face_count(),
+    );
+
+    let face = delaunay
+        .faces()
+        .find(|face| !face.is_infinite())
+        .unwrap()
+        .id();
+    assert_eq!(
+        delaunay.split_face(face, Point::new(5.0, 5.0)),
+        Err(EditError::Degenerate)
+    );
+
+    let edge = delaunay
+        .edges()
+        .find(|edge| !edge.is_infinite())
+        .unwrap()
+        .id();
+    assert_eq!(
+        delaunay.split_edge(edge, Point::new(5.0, -5.0)),
+        Err(EditError::Degenerate)
+    );
+
+    assert_eq!(delaunay.vertex_count(), vertices);
+    assert_eq!(delaunay.edge_count(), edges);
+    assert_eq!(delaunay.face_count(), faces);
+    assert_eq!(delaunay.validate(), Ok(()));
+}
@@ -0,0 +1,124 @@
+use graph::{Delaunay, Point};
+use std::f64::consts::FRAC_PI_2;
+
+mod common;
+
+use common::close;
+
+// A 3-4-5 right triangle.
+fn right_triangle() -> Delaunay {
+    Delaunay::from(vec![
+        Point::new(0.0, 0.0),
+        Point::new(4.0, 0.0),
+        Point::new(0.0, 3.0),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn faces() {
+    let delaunay = right_triangle();
+    let face = delaunay.faces().find(|face| !face.is_infinite()).unwrap();
+
+    assert!(close(face.area().unwrap(), 6.0));
+    let centroid = face.centroid().unwrap();
+    assert!(close(centroid.x, 4.0 / 3.0) && close(centroid.y, 1.0));
+    let center = face.circumcenter().unwrap();
+    assert!(close(center.x, 2.0) && close(center.y, 1.5));
+    assert!(close(face.circumradius().unwrap(), 2.5));
+    let (center, radius) = face.incircle().unwrap();
+    assert!(close(center.x, 1.0) && close(center.y, 1.0) && close(radius, 1.0));
+    assert!(close(face.aspect_ratio().unwrap(), 1.25));
+
+    let mut angles = face.angles().unwrap();
+    angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let expected = [0.75f64.atan(), (4.0f64 / 3.0).atan(), FRAC_PI_2];
+    assert!(angles.iter().zip(&expected).all(|(&a, &b)| close(a, b)));
+
+    assert_eq!(face.contains(&Point::new(1.0, 1.0)), Some(true));
+    assert_eq!(face.contains(&Point::new(2.0, 0.0)), Some(true));
+    assert_eq!(face.contains(&Point::new(3.0, 3.0)), Some(false));
+}
+
+#[test]
+fn equilateral() {
+    let delaunay = Delaunay::from(vec![
+        Point::new(0.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(1.0, 3f64.sqrt()),
+    ])
+    .unwrap();
+    let face = delaunay.faces().find(|face| !face.is_infinite()).unwrap();
+
+    assert!(close(face.aspect_ratio().unwrap(), 1.0));
+    assert!(face
+        .angles()
+        .unwrap()
+        .iter()
+        .all(|&angle| close(angle, FRAC_PI_2 * 2.0 / 3.0)));
+}
+
+#[test]
+fn edges() {
+    let delaunay = right_triangle();
+    let hypotenuse = delaunay
+        .edges()
+        .find(|edge| edge.length().is_some_and(|length| close(length, 5.0)))
+        .unwrap();
+
+    let midpoint = hypotenuse.midpoint().unwrap();
+    assert!(close(midpoint.x, 2.0) && close(midpoint.y, 1.5));
+    let direction = hypotenuse.direction().unwrap();
+    let (origin, destination) = hypotenuse.vertices();
+    let (p0, p1) = (
+        origin.position().left().unwrap(),
+        destination.position().left().unwrap(),
+    );
+    assert!(close(direction.x, (p1.x - p0.x) / 5.0) && close(direction.y, (p1.y - p0.y) / 5.0));
+
+    let mut lengths = delaunay
+        .edges()
+        .filter_map(|edge| edge.length())
+        .collect::<Vec<_>>();
+    lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(lengths, vec![3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn infinite_elements() {
+    let delaunay = right_triangle();
+
+    for face in delaunay.faces().filter(|face| face.is_infinite()) {
+        assert!(face.points().is_none());
+        assert!(face.area().is_none() && face.centroid().is_none());
+        assert!(face.circumcenter().is_none() && face.circumradius().is_none());
+        assert!(face.incircle().is_none() && face.aspect_ratio().is_none());
+        assert!(face.angles().is_none());
+        assert!(face.contains(&Point::new(1.0, 1.0)).is_none());
+    }
+    for edge in delaunay.edges().filter(|edge| edge.is_infinite()) {
+        assert!(edge.length().is_none());
+        assert!(edge.midpoint().is_none() && edge.direction().is_none());
+    }
+}
+
+#[test]
+fn flat_triangles() {
+    // The collinear bottom row stacks a flat triangle under the hull.
+    let delaunay = Delaunay::from(vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(1.0, 1.0),
+    ])
+    .unwrap();
+    let flat = delaunay
+        .faces()
+        .filter(|face| face.area() == Some(0.0))
+        .collect::<Vec<_>>();
+    assert_eq!(flat.len(), 1);
+
+    let face = flat[0];
+    assert!(face.circumcenter().is_none() && face.circumradius().is_none());
+    assert!(face.incircle().is_none() && face.aspect_ratio().is_none());
+}
@@ -0,0 +1,78 @@
+use graph::gis::{read_wkt_points, write_wkt};
+use graph::{Delaunay, Point};
+
+fn delaunay() -> Delaunay {
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(0.0, 1.0),
+        Point::new(1.0, 1.2),
+    ];
+    Delaunay::from(points).unwrap()
+}
+
+#[test]
+fn wkt_text_is_quoted() {
+    let mut delaunay = delaunay();
+    let labels = (0..delaunay.face_count())
+        .map(|face| format!("face\t{}\n\"{}\"", face, face))
+        .collect::<Vec<_>>();
+    delaunay
+        .face_attributes_mut()
+        .insert("label", labels)
+        .unwrap();
+    delaunay.face_attributes_mut().add::<u32>("tab\tname");
+
+    let mut wkt = Vec::new();
+    write_wkt(&delaunay, &mut wkt).unwrap();
+    let wkt = String::from_utf8(wkt).unwrap();
+
+    assert!(wkt.starts_with("wkt\tlabel\t\"tab\tname\"\n"));
+    for face in delaunay.faces().filter(|face| !face.is_infinite()) {
+        let id = face.id().index();
+        let cell = format!("\t\"face\t{}\n\"\"{}\"\"\"\t0\n", id, id);
+        assert!(wkt.contains(&cell), "{}", cell);
+    }
+}
+
+#[test]
+fn wkt_points() {
+    let wkt = "POINT (1 2)\nMULTIPOINT ((3 4), (5 6))\nMULTIPOINT Z (7 8 9, 10 11 12)\nPOINT EMPTY";
+    let points = read_wkt_points::<_, f64>(wkt.as_bytes()).unwrap();
+    let coords = points
+        .iter()
+        .map(|point| (point.x, point.y))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        coords,
+        [(1.0, 2.0), (3.0, 4.0), (5.0, 6.0), (7.0, 8.0), (10.0, 11.0)]
+    );
+
+    assert!(read_wkt_points::<_, f64>("LINESTRING (0 0, 1 1)".as_bytes()).is_err());
+}
+
+#[cfg(feature = "geojson")]
+#[test]
+fn geojson() {
+    use graph::gis::{read_geojson_points, write_geojson};
+
+    let delaunay = delaunay();
+    let mut json = Vec::new();
+    write_geojson(&delaunay, &mut json).unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let features = value["features"].as_array().unwrap();
+    let finite = delaunay.faces().filter(|face| !face.is_infinite()).count();
+    assert_eq!(features.len(), finite);
+
+    let points = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 2]}},
+        {"type": "Feature", "geometry": null},
+        {"type": "Feature", "geometry": {"type": "MultiPoint", "coordinates": [[3, 4], [5, 6]]}}
+    ]}"#;
+    let points = read_geojson_points::<_, f64>(points.as_bytes()).unwrap();
+    let coords = points
+        .iter()
+        .map(|point| (point.x, point.y))
+        .collect::<Vec<_>>();
+    assert_eq!(coords, [(1.0, 2.0), (3.0, 4.0), (5.0, 6.0)]);
+}
@@ -0,0 +1,211 @@
+use either::{Left, Right};
+use graph::{Delaunay, FaceId, HalfEdgeId, Point, Vertex, VertexId};
+use std::collections::BTreeSet;
+
+// A square around a centre point: four triangles meeting at the centre and four infinite
+// faces outside the square.
+fn square() -> Delaunay {
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(2.0, 2.0),
+        Point::new(0.0, 2.0),
+        Point::new(1.0, 1.0),
+    ];
+    Delaunay::from(points).unwrap()
+}
+
+fn position(vertex: Vertex<'_, ()>) -> (f64, f64) {
+    let p = vertex.position().left().unwrap();
+    (p.x, p.y)
+}
+
+#[test]
+fn counts() {
+    let delaunay = square();
+    assert_eq!(delaunay.vertex_count(), 6);
+    assert_eq!(delaunay.edge_count(), 12);
+    assert_eq!(delaunay.face_count(), 8);
+    assert_eq!(
+        delaunay.faces().filter(|face| face.is_infinite()).count(),
+        4
+    );
+}
+
+#[test]
+fn half_edges() {
+    let delaunay = square();
+    let mut seen = BTreeSet::new();
+
+    for edge in delaunay.edges() {
+        for half in [edge, edge.twin()] {
+            assert!(seen.insert(half.half_edge_id()));
+            let twin = half.twin();
+            assert_eq!(twin.twin().half_edge_id(), half.half_edge_id());
+            assert_eq!(twin.id(), half.id());
+            assert_eq!(twin.origin().id(), half.destination().id());
+            assert_eq!(twin.destination().id(), half.origin().id());
+            assert_eq!(twin.left_face().id(), half.right_face().id());
+            assert_eq!(twin.right_face().id(), half.left_face().id());
+
+            assert_eq!(half.next().origin().id(), half.destination().id());
+            assert_eq!(half.prev().destination().id(), half.origin().id());
+            assert_eq!(half.next().prev().half_edge_id(), half.half_edge_id());
+            assert_eq!(half.prev().next().half_edge_id(), half.half_edge_id());
+            assert_eq!(half.next().left_face().id(), half.left_face().id());
+
+            let third = half.next().next().next();
+            assert_eq!(third.half_edge_id(), half.half_edge_id());
+        }
+    }
+    assert_eq!(seen.len(), 24);
+}
+
+#[test]
+fn faces() {
+    let delaunay = square();
+
+    for face in delaunay.faces() {
+        assert_eq!(face.edges().count(), 3);
+        assert!(face.edges().all(|edge| edge.left_face().id() == face.id()));
+        for (edge, neighbor) in face.edges().zip(face.neighbors()) {
+            assert_eq!(edge.right_face().id(), neighbor.id());
+        }
+        if face.is_infinite() {
+            continue;
+        }
+
+        // Finite faces run counter-clockwise around the centre and one side of the square.
+        let corners = face.vertices().map(position).collect::<Vec<_>>();
+        assert!(corners.contains(&(1.0, 1.0)));
+        let ((x0, y0), (x1, y1), (x2, y2)) = (corners[0], corners[1], corners[2]);
+        assert_eq!((x1 - x0) * (y2 - y0) - (y1 - y0) * (x2 - x0), 2.0);
+        assert!(face.neighbors().filter(|n| n.is_infinite()).count() == 1);
+    }
+}
+
+#[test]
+fn outgoing_edges() {
+    let delaunay = square();
+    let centre = delaunay
+        .vertices()
+        .find(|&vertex| !vertex.is_infinite() && position(vertex) == (1.0, 1.0))
+        .unwrap();
+
+    // Clockwise: each corner follows the previous one a quarter turn clockwise.
+    let corners = centre
+        .outgoing_edges()
+        .map(|edge| position(edge.destination()))
+        .collect::<Vec<_>>();
+    assert_eq!(corners.len(), 4);
+    for (i, &(x0, y0)) in corners.iter().enumerate() {
+        let (x1, y1) = corners[(i + 1) % 4];
+        assert_eq!((x0 - 1.0, y0 - 1.0), (-(y1 - 1.0), x1 - 1.0));
+    }
+
+    assert!(centre
+        .outgoing_edges()
+        .all(|edge| edge.origin().id() == centre.id()));
+    assert!(centre
+        .incoming_edges()
+        .all(|edge| edge.destination().id() == centre.id()));
+    let neighbors = centre.neighbors().map(position).collect::<Vec<_>>();
+    assert_eq!(neighbors, corners);
+    assert_eq!(centre.faces().filter(|face| !face.is_infinite()).count(), 4);
+
+    // The infinite vertex sees the hull, also clockwise.
+    let infinite = delaunay
+        .vertices()
+        .find(|vertex| vertex.is_infinite())
+        .unwrap();
+    let hull = infinite
+        .outgoing_edges()
+        .map(|edge| position(edge.destination()))
+        .collect::<Vec<_>>();
+    assert_eq!(hull.len(), 4);
+    assert!(!hull.contains(&(1.0, 1.0)));
+}
+
+#[test]
+fn dual() {
+    let mut delaunay = square();
+    let faces = delaunay.faces().map(|face| face.id()).collect::<Vec<_>>();
+    let vertices = delaunay
+        .vertices()
+        .map(|vertex| vertex.id())
+        .collect::<Vec<_>>();
+    let edges = (0..delaunay.edge_count()).map(|edge| edge as u32).collect();
+    delaunay
+        .face_attributes_mut()
+        .insert("face", faces)
+        .unwrap();
+    delaunay
+        .vertex_attributes_mut()
+        .insert("vertex", vertices)
+        .unwrap();
+    delaunay
+        .edge_attributes_mut()
+        .insert("edge", edges)
+        .unwrap();
+
+    // Finite triangles become their centroids, infinite faces keep no position.
+    let dual = delaunay.dual(|face| {
+        if face.is_infinite() {
+            return Right(());
+        }
+        let (x, y) = face
+            .vertices()
+            .map(position)
+            .fold((0.0, 0.0), |(x, y), (px, py)| (x + px / 3.0, y + py / 3.0));
+        Left(Point::new(x, y))
+    });
+    assert_eq!(dual.vertex_count(), 8);
+    assert_eq!(dual.face_count(), 6);
+    assert_eq!(dual.edge_count(), 12);
+    assert_eq!(
+        dual.vertices()
+            .filter(|vertex| vertex.is_infinite())
+            .count(),
+        4
+    );
+
+    for vertex in dual.vertices() {
+        let face = FaceId::new(vertex.id().index());
+        assert_eq!(vertex.attribute::<FaceId>("face"), Some(&face));
+        assert!(vertex.attribute::<VertexId>("vertex").is_none());
+    }
+    for face in dual.faces() {
+        let vertex = delaunay.vertex(VertexId::new(face.id().index()));
+        assert_eq!(face.attribute::<VertexId>("vertex"), Some(&vertex.id()));
+        assert_eq!(face.edges().count(), vertex.outgoing_edges().count());
+    }
+    for edge in dual.edges() {
+        assert_eq!(
+            edge.attribute::<u32>("edge"),
+            Some(&(edge.id().index() as u32))
+        );
+    }
+
+    // Dual half-edges run from the left face of their half-edge to its right face.
+    for half in 0..24 {
+        let primal = delaunay.half_edge(HalfEdgeId::new(half));
+        let crossing = dual.half_edge(HalfEdgeId::new(half));
+        assert_eq!(
+            crossing.origin().id().index(),
+            primal.left_face().id().index()
+        );
+        assert_eq!(
+            crossing.destination().id().index(),
+            primal.right_face().id().index()
+        );
+    }
+
+    // The dual face around the centre is the square of the four triangle centroids.
+    let centre = delaunay
+        .vertices()
+        .find(|&vertex| !vertex.is_infinite() && position(vertex) == (1.0, 1.0))
+        .unwrap();
+    let cell = dual.face(FaceId::new(centre.id().index()));
+    let area = cell.area().unwrap();
+    assert!((area - 8.0 / 9.0).abs() < 1e-12, "{}", area);
+}
@@ -0,0 +1,85 @@
+use graph::{lloyd_relax, Bounds, Delaunay, Lloyd, Point, RelaxError, Voronoi};
+
+mod common;
+
+// Areas of the Voronoi cells of `points`, clipped to the unit square.
+fn areas(points: &[Point]) -> Vec<f64> {
+    let delaunay = Delaunay::from(points.to_vec()).unwrap();
+    let clipped = Voronoi::from(&delaunay).clipped(&Bounds::unit()).unwrap();
+    clipped.faces().filter_map(|face| face.area()).collect()
+}
+
+fn deviation(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance =
+        values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / values.len() as f64;
+    variance.sqrt()
+}
+
+#[test]
+fn relax() {
+    let mut points = common::points(200);
+
+    assert_eq!(lloyd_relax(&mut points, &Bounds::unit(), 5), Ok(5));
+    let bounds = Bounds::unit();
+    assert!(points.iter().all(|point| bounds.contains(point)));
+}
+
+#[test]
+fn collinear_sites() {
+    let mut points = (0..10)
+        .map(|i| Point::new(i as f64 / 10.0, 0.5))
+        .collect::<Vec<Point>>();
+
+    assert_eq!(
+        lloyd_relax(&mut points, &Bounds::unit(), 5),
+        Err(RelaxError::Triangulation)
+    );
+}
+
+#[test]
+fn tolerance() {
+    let mut points = common::points(200);
+    let lloyd = Lloyd::new(Bounds::unit(), 100).with_tolerance(1e-3);
+
+    let iterations = lloyd.relax(&mut points).unwrap();
+    assert!(iterations > 1 && iterations < 100, "{}", iterations);
+
+    // The sites have settled: one more round moves none of them past the tolerance.
+    let mut settled = points.clone();
+    assert_eq!(Lloyd::new(Bounds::unit(), 1).relax(&mut settled), Ok(1));
+    assert!(points
+        .iter()
+        .zip(&settled)
+        .all(|(a, b)| (a.x - b.x).hypot(a.y - b.y) <= 1e-3));
+}
+
+#[test]
+fn density() {
+    let mut uniform = common::points(50);
+    let mut weighted = uniform.clone();
+
+    lloyd_relax(&mut uniform, &Bounds::unit(), 40).unwrap();
+    Lloyd::new(Bounds::unit(), 40)
+        .with_density(|p: &Point| 1.0 + 9.0 * p.x)
+        .relax(&mut weighted)
+        .unwrap();
+
+    // Sites crowd towards the dense right side of the square.
+    let right = |points: &[Point]| points.iter().filter(|p| p.x > 0.5).count();
+    assert!(right(&weighted) > right(&uniform) + 3);
+    let mean = |points: &[Point]| points.iter().map(|p| p.x).sum::<f64>() / points.len() as f64;
+    assert!(mean(&weighted) > mean(&uniform) + 0.03);
+}
+
+#[test]
+fn evens_out_cells() {
+    let mut points = common::points(200);
+    let before = areas(&points);
+    assert_eq!(before.len(), 200);
+
+    lloyd_relax(&mut points, &Bounds::unit(), 10).unwrap();
+    let after = areas(&points);
+    assert!((after.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(deviation(&after) < deviation(&before) / 2.0);
+}
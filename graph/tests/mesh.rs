@@ -0,0 +1,305 @@
+use graph::{Delaunay, MeshWriter, Point, VertexId};
+use std::convert::TryInto;
+
+mod common;
+
+// Corners of the finite faces as graph vertex ids.
+fn triangles(delaunay: &Delaunay) -> Vec<Vec<usize>> {
+    delaunay
+        .faces()
+        .filter(|face| !face.is_infinite())
+        .map(|face| face.vertices().map(|vertex| vertex.id().index()).collect())
+        .collect()
+}
+
+#[test]
+fn obj_counts_from_one() {
+    let delaunay = common::delaunay(30);
+    let mut obj = Vec::new();
+    MeshWriter::new(&delaunay).write_obj(&mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+
+    let faces = obj
+        .lines()
+        .filter_map(|line| line.strip_prefix("f "))
+        .map(|line| line.split(' ').map(|v| v.parse().unwrap()).collect())
+        .collect::<Vec<Vec<usize>>>();
+    assert_eq!(faces, triangles(&delaunay));
+    let vertices = obj.lines().filter(|line| line.starts_with("v ")).count();
+    assert_eq!(vertices, delaunay.vertex_count() - 1);
+}
+
+#[test]
+fn off_and_ply_count_from_zero() {
+    let delaunay = common::delaunay(30);
+    let expected = triangles(&delaunay)
+        .into_iter()
+        .map(|face| face.into_iter().map(|v| v - 1).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let writer = MeshWriter::new(&delaunay);
+
+    let mut off = Vec::new();
+    writer.write_off(&mut off).unwrap();
+    let off = String::from_utf8(off).unwrap();
+    let vertices = delaunay.vertex_count() - 1;
+    let faces = off
+        .lines()
+        .skip(2 + vertices)
+        .map(|line| {
+            line.split(' ')
+                .skip(1)
+                .map(|v| v.parse().unwrap())
+                .collect()
+        })
+        .collect::<Vec<Vec<usize>>>();
+    assert_eq!(faces, expected);
+
+    let mut ply = Vec::new();
+    writer.write_ply(&mut ply).unwrap();
+    let ply = String::from_utf8(ply).unwrap();
+    assert!(ply.contains(&format!("element vertex {}\n", vertices)));
+    let body = ply.split("end_header\n").nth(1).unwrap();
+    let faces = body
+        .lines()
+        .skip(vertices)
+        .map(|line| {
+            line.split(' ')
+                .skip(1)
+                .map(|v| v.parse().unwrap())
+                .collect()
+        })
+        .collect::<Vec<Vec<usize>>>();
+    assert_eq!(faces, expected);
+}
+
+// Delaunay of `common::points(30)` with a height `x + y`, a colour and a weight on every
+// finite vertex.
+fn decorated() -> Delaunay {
+    let mut delaunay = common::delaunay(30);
+    let count = delaunay.vertex_count();
+    let positions = (0..count)
+        .map(|v| delaunay.vertex(VertexId::new(v)).position().left())
+        .collect::<Vec<_>>();
+    let heights = positions
+        .iter()
+        .map(|p| p.map_or(0.0, |p| p.x + p.y))
+        .collect::<Vec<_>>();
+    let colors = (0..count)
+        .map(|v| [v as u8, 2 * v as u8, 255 - v as u8])
+        .collect::<Vec<_>>();
+    let weights = (0..count).map(|v| v as f64 / 4.0).collect::<Vec<_>>();
+
+    let attributes = delaunay.vertex_attributes_mut();
+    attributes.insert("height", heights).unwrap();
+    attributes.insert("color", colors).unwrap();
+    attributes.insert("weight", weights).unwrap();
+    delaunay
+}
+
+fn take<'a>(bytes: &mut &'a [u8], count: usize) -> &'a [u8] {
+    let (head, tail) = bytes.split_at(count);
+    *bytes = tail;
+    head
+}
+
+#[test]
+fn flat_faces_are_skipped() {
+    // The hull of a 5 by 5 grid keeps 12 flat triangles along its sides.
+    let points = (0..25)
+        .map(|i| Point::new((i % 5) as f64, (i / 5) as f64))
+        .collect::<Vec<_>>();
+    let delaunay = Delaunay::from(points).unwrap();
+    let writer = MeshWriter::new(&delaunay);
+
+    let mut obj = Vec::new();
+    writer.write_obj(&mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    assert_eq!(
+        obj.lines().filter(|line| line.starts_with("f ")).count(),
+        32
+    );
+
+    let mut stl = Vec::new();
+    writer.write_stl(&mut stl).unwrap();
+    let stl = String::from_utf8(stl).unwrap();
+    let normals = stl
+        .lines()
+        .filter_map(|line| line.strip_prefix("facet normal "))
+        .map(|line| {
+            line.split(' ')
+                .map(|value| value.parse().unwrap())
+                .collect::<Vec<f32>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(normals, vec![vec![0.0, 0.0, 1.0]; 32]);
+
+    let mut stl = Vec::new();
+    writer.write_stl_binary(&mut stl).unwrap();
+    assert_eq!(stl[80..84], 32u32.to_le_bytes());
+    assert_eq!(stl.len(), 84 + 32 * 50);
+}
+
+#[test]
+fn obj_and_off_with_height_and_colour() {
+    let delaunay = decorated();
+    let writer = MeshWriter::new(&delaunay)
+        .with_z("height")
+        .with_color("color");
+    let heights = delaunay.vertex_attributes().get::<f64>("height").unwrap();
+
+    let mut obj = Vec::new();
+    writer.write_obj(&mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    let vertices = obj.lines().filter_map(|line| line.strip_prefix("v "));
+    for (v, line) in (1..).zip(vertices) {
+        let values = line
+            .split(' ')
+            .map(|value| value.parse().unwrap())
+            .collect::<Vec<f64>>();
+        let point = delaunay.vertex(VertexId::new(v)).position().left().unwrap();
+        let color = [v as u8, 2 * v as u8, 255 - v as u8];
+        let expected = [
+            point.x,
+            point.y,
+            heights[v],
+            f64::from(color[0]) / 255.0,
+            f64::from(color[1]) / 255.0,
+            f64::from(color[2]) / 255.0,
+        ];
+        assert_eq!(values, expected);
+    }
+
+    let mut off = Vec::new();
+    writer.write_off(&mut off).unwrap();
+    let off = String::from_utf8(off).unwrap();
+    let mut lines = off.lines();
+    assert_eq!(lines.next(), Some("COFF"));
+    lines.next();
+    for (v, line) in (1..delaunay.vertex_count()).zip(lines) {
+        let values = line.split(' ').collect::<Vec<_>>();
+        assert_eq!(values[2].parse::<f64>().unwrap(), heights[v]);
+        let color = format!("{} {} {}", v as u8, 2 * v as u8, 255 - v as u8);
+        assert_eq!(values[3..].join(" "), format!("{} 255", color));
+    }
+}
+
+#[test]
+fn binary_ply_matches_ascii() {
+    let delaunay = decorated();
+    let writer = MeshWriter::new(&delaunay)
+        .with_z("height")
+        .with_color("color")
+        .with_property("weight");
+    let vertices = delaunay.vertex_count() - 1;
+    let faces = triangles(&delaunay);
+
+    let mut ascii = Vec::new();
+    writer.write_ply(&mut ascii).unwrap();
+    let ascii = String::from_utf8(ascii).unwrap();
+    let (header, body) = ascii.split_at(ascii.find("end_header\n").unwrap() + 11);
+    assert!(header.contains(
+        "property uchar red\nproperty uchar green\nproperty uchar blue\nproperty double weight\n"
+    ));
+    let rows = body.lines().collect::<Vec<_>>();
+    assert_eq!(rows.len(), vertices + faces.len());
+
+    let mut binary = Vec::new();
+    writer.write_ply_binary(&mut binary).unwrap();
+    let mut bytes = &binary[..];
+    let expected = header.replace("format ascii", "format binary_little_endian");
+    assert_eq!(take(&mut bytes, expected.len()), expected.as_bytes());
+
+    let double = |bytes: &mut &[u8]| f64::from_le_bytes(take(bytes, 8).try_into().unwrap());
+    for row in &rows[..vertices] {
+        let values = row.split(' ').collect::<Vec<_>>();
+        for value in &values[..3] {
+            assert_eq!(double(&mut bytes), value.parse::<f64>().unwrap());
+        }
+        for value in &values[3..6] {
+            assert_eq!(take(&mut bytes, 1)[0], value.parse::<u8>().unwrap());
+        }
+        assert_eq!(double(&mut bytes), values[6].parse::<f64>().unwrap());
+    }
+    for (row, face) in rows[vertices..].iter().zip(&faces) {
+        assert_eq!(take(&mut bytes, 1)[0], 3);
+        let corners = (0..3)
+            .map(|_| i32::from_le_bytes(take(&mut bytes, 4).try_into().unwrap()) as usize + 1)
+            .collect::<Vec<_>>();
+        assert_eq!(&corners, face);
+        let row = row
+            .split(' ')
+            .skip(1)
+            .map(|v| v.parse::<usize>().unwrap() + 1);
+        assert_eq!(row.collect::<Vec<_>>(), corners);
+    }
+    assert!(bytes.is_empty());
+
+    let weights = rows[..vertices]
+        .iter()
+        .map(|row| row.split(' ').nth(6).unwrap().parse::<f64>().unwrap())
+        .collect::<Vec<_>>();
+    let expected = (1..=vertices).map(|v| v as f64 / 4.0).collect::<Vec<_>>();
+    assert_eq!(weights, expected);
+}
+
+#[test]
+fn binary_stl_matches_ascii() {
+    let delaunay = decorated();
+    let writer = MeshWriter::new(&delaunay).with_z("height");
+    let faces = triangles(&delaunay).len();
+
+    let mut ascii = Vec::new();
+    writer.write_stl(&mut ascii).unwrap();
+    let ascii = String::from_utf8(ascii).unwrap();
+    let numbers = |line: &str| {
+        line.split(' ')
+            .filter_map(|value| value.parse::<f32>().ok())
+            .collect::<Vec<_>>()
+    };
+    let facets = ascii
+        .lines()
+        .filter(|line| line.starts_with("facet normal ") || line.starts_with("vertex "))
+        .map(numbers)
+        .collect::<Vec<_>>();
+    assert_eq!(facets.len(), 4 * faces);
+
+    let mut binary = Vec::new();
+    writer.write_stl_binary(&mut binary).unwrap();
+    let mut bytes = &binary[..];
+    take(&mut bytes, 80);
+    assert_eq!(take(&mut bytes, 4), (faces as u32).to_le_bytes());
+    for facet in facets.chunks(4) {
+        for row in facet {
+            let values = (0..3)
+                .map(|_| f32::from_le_bytes(take(&mut bytes, 4).try_into().unwrap()))
+                .collect::<Vec<_>>();
+            assert_eq!(&values, row);
+        }
+        assert_eq!(take(&mut bytes, 2), [0, 0]);
+
+        // Heights tilt every facet of the plane `z = x + y` the same way.
+        let normal = &facet[0];
+        let expected = 1.0 / 3f32.sqrt();
+        assert!((normal[0] + expected).abs() < 1e-6, "{:?}", normal);
+        assert!((normal[1] + expected).abs() < 1e-6, "{:?}", normal);
+        assert!((normal[2] - expected).abs() < 1e-6, "{:?}", normal);
+        for corner in &facet[1..] {
+            assert!((corner[2] - (corner[0] + corner[1])).abs() < 1e-6);
+        }
+    }
+    assert!(bytes.is_empty());
+}
+
+#[test]
+fn missing_attributes() {
+    let delaunay = decorated();
+    let writers = [
+        MeshWriter::new(&delaunay).with_z("color"),
+        MeshWriter::new(&delaunay).with_color("height"),
+        MeshWriter::new(&delaunay).with_property("missing"),
+    ];
+    for writer in &writers {
+        let error = writer.write_ply(Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
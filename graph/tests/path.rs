@@ -0,0 +1,145 @@
+use graph::{Path, PathFinder, VertexId};
+
+mod common;
+
+fn ids(path: &Path) -> Vec<VertexId> {
+    path.vertices.iter().map(|vertex| vertex.id()).collect()
+}
+
+#[test]
+fn a_star_matches_dijkstra() {
+    let delaunay = common::delaunay(500);
+    let finder = PathFinder::new(&delaunay);
+    let from = VertexId::new(1);
+    let field = finder.distances(from);
+
+    for to in (2..delaunay.vertex_count()).step_by(7).map(VertexId::new) {
+        let dijkstra = finder.dijkstra(from, to).unwrap();
+        let a_star = finder.a_star(from, to).unwrap();
+
+        assert!((dijkstra.cost - a_star.cost).abs() < 1e-12, "{:?}", to);
+        assert!((dijkstra.cost - field.distance(to).unwrap()).abs() < 1e-12);
+        assert_eq!(a_star.vertices.first().unwrap().id(), from);
+        assert_eq!(a_star.vertices.last().unwrap().id(), to);
+    }
+}
+
+#[test]
+fn a_star_matches_dijkstra_with_weights() {
+    let delaunay = common::delaunay(500);
+    let blocked = (10..40).map(VertexId::new);
+    let finder = PathFinder::new(&delaunay)
+        .with_weight(|edge| edge.length().unwrap_or(f64::INFINITY).sqrt())
+        .with_blocked(blocked);
+    let from = VertexId::new(1);
+
+    for to in (40..delaunay.vertex_count()).step_by(11).map(VertexId::new) {
+        let dijkstra = finder.dijkstra(from, to).map(|path| path.cost);
+        let a_star = finder.a_star(from, to).map(|path| path.cost);
+
+        match (dijkstra, a_star) {
+            (Some(d), Some(a)) => assert!((d - a).abs() < 1e-12, "{:?}", to),
+            (d, a) => assert_eq!(d.is_none(), a.is_none()),
+        }
+    }
+}
+
+#[test]
+fn paths_avoid_blocked_vertices() {
+    let delaunay = common::delaunay(500);
+    // A wall across the middle of the square with a gap at the top.
+    let blocked = delaunay
+        .vertices()
+        .filter(|vertex| {
+            let p = vertex.position().left();
+            p.is_some_and(|p| (p.x - 0.5).abs() < 0.1 && p.y < 0.8)
+        })
+        .map(|vertex| vertex.id())
+        .collect::<Vec<_>>();
+    let open = PathFinder::new(&delaunay);
+    let finder = PathFinder::new(&delaunay).with_blocked(blocked.iter().copied());
+
+    let side = |inside: fn(f64) -> bool| {
+        delaunay
+            .vertices()
+            .filter(|vertex| vertex.position().left().is_some_and(|p| inside(p.x)))
+            .map(|vertex| vertex.id())
+            .take(5)
+            .collect::<Vec<_>>()
+    };
+    let (left, right) = (side(|x| x < 0.2), side(|x| x > 0.8));
+
+    let mut detours = 0;
+    for &from in &left {
+        for &to in &right {
+            for path in [finder.dijkstra(from, to), finder.a_star(from, to)] {
+                let path = path.unwrap();
+                let ids = ids(&path);
+                assert!(ids.iter().all(|id| !blocked.contains(id)), "{:?}", ids);
+                for pair in path.vertices.windows(2) {
+                    assert!(pair[0].neighbors().any(|n| n.id() == pair[1].id()));
+                }
+
+                let shortest = open.dijkstra(from, to).unwrap().cost;
+                assert!(path.cost >= shortest - 1e-12);
+                if path.cost > shortest + 1e-12 {
+                    detours += 1;
+                }
+            }
+        }
+    }
+    assert!(detours > 0);
+}
+
+#[test]
+fn blocked_ends_have_no_path() {
+    let delaunay = common::delaunay(100);
+    let (from, to, other) = (VertexId::new(1), VertexId::new(2), VertexId::new(3));
+
+    for blocked in [from, to] {
+        let finder = PathFinder::new(&delaunay).with_blocked(vec![blocked]);
+        assert!(finder.dijkstra(from, to).is_none());
+        assert!(finder.a_star(from, to).is_none());
+    }
+
+    let finder = PathFinder::new(&delaunay).with_blocked(vec![from]);
+    let field = finder.distances(from);
+    assert!(field.distances().iter().all(Option::is_none));
+    assert!(field.path_to(other).is_none());
+}
+
+#[test]
+fn disconnected_target_has_no_path() {
+    let delaunay = common::delaunay(100);
+    let from = VertexId::new(1);
+    let to = (2..delaunay.vertex_count())
+        .map(VertexId::new)
+        .find(|&to| delaunay.vertex(to).neighbors().all(|n| n.id() != from))
+        .unwrap();
+    let ring = delaunay.vertex(to).neighbors().map(|vertex| vertex.id());
+    let finder = PathFinder::new(&delaunay).with_blocked(ring);
+
+    assert!(finder.dijkstra(from, to).is_none());
+    assert!(finder.a_star(from, to).is_none());
+    let field = finder.distances(from);
+    assert!(field.distance(to).is_none());
+    assert!(field.path_to(to).is_none());
+}
+
+#[test]
+fn distance_field_paths_match_dijkstra() {
+    let delaunay = common::delaunay(300);
+    let finder = PathFinder::new(&delaunay)
+        .with_weight(|edge| edge.length().unwrap_or(f64::INFINITY).powi(2));
+    let from = VertexId::new(5);
+    let field = finder.distances(from);
+    assert_eq!(field.source().id(), from);
+
+    for to in (1..delaunay.vertex_count()).map(VertexId::new) {
+        let path = field.path_to(to).unwrap();
+        let dijkstra = finder.dijkstra(from, to).unwrap();
+        assert_eq!(ids(&path), ids(&dijkstra), "{:?}", to);
+        assert!((path.cost - dijkstra.cost).abs() < 1e-12, "{:?}", to);
+        assert_eq!(Some(path.cost), field.distance(to));
+    }
+}
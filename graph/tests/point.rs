@@ -0,0 +1,96 @@
+use graph::Point;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn widen(p: &Point<f32>) -> Point {
+    Point::new(p.x as f64, p.y as f64)
+}
+
+#[test]
+fn orientation() {
+    let (a, b, c) = (
+        Point::new(0.0f32, 0.0),
+        Point::new(1.0f32, 0.0),
+        Point::new(0.0f32, 1.0),
+    );
+    assert!(Point::is_ccw(&a, &b, &c));
+    assert!(!Point::is_ccw(&a, &c, &b));
+
+    let d = Point::new(0.5f32, 0.0);
+    assert!(!Point::is_ccw(&a, &d, &b));
+    assert!(!Point::is_ccw(&a, &b, &d));
+}
+
+#[test]
+fn near_collinear_orientation() {
+    // Points one ulp off the diagonal, where the plain f64 estimate cannot decide.
+    let (a, b) = (Point::new(0.1f32, 0.1), Point::new(1e4f32, 1e4));
+    let on = Point::new(3.0f32, 3.0);
+    let above = Point::new(3.0f32, 3.0f32 + f32::EPSILON * 2.0);
+    let below = Point::new(3.0f32, 3.0f32 - f32::EPSILON * 2.0);
+
+    assert!(!Point::is_ccw(&a, &b, &on) && !Point::is_ccw(&b, &a, &on));
+    assert!(Point::is_ccw(&a, &b, &above) && !Point::is_ccw(&b, &a, &above));
+    assert!(!Point::is_ccw(&a, &b, &below) && Point::is_ccw(&b, &a, &below));
+}
+
+#[test]
+fn consistent_with_f64() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..10_000 {
+        // Three points close to a line through the origin, in f32.
+        let slope = rng.gen_range(-4.0f32..4.0);
+        let mut near = || {
+            let x = rng.gen_range(-100.0f32..100.0);
+            let y = x * slope;
+            let ulps = rng.gen_range(-2i32..=2);
+            Point::new(x, f32::from_bits((y.to_bits() as i32 + ulps) as u32))
+        };
+        let (a, b, c) = (near(), near(), near());
+
+        let ccw = Point::is_ccw(&a, &b, &c);
+        assert_eq!(ccw, Point::is_ccw(&widen(&a), &widen(&b), &widen(&c)));
+        assert_eq!(ccw, Point::is_ccw(&b, &c, &a));
+        assert_eq!(ccw, Point::is_ccw(&c, &a, &b));
+        assert!(!(ccw && Point::is_ccw(&a, &c, &b)));
+    }
+}
+
+#[test]
+fn circle() {
+    // Clockwise, as the triangulator passes its triangles.
+    let (a, b, c) = (
+        Point::new(0.0f32, 0.0),
+        Point::new(1.0f32, 1.0),
+        Point::new(1.0f32, 0.0),
+    );
+    assert!(Point::in_circle(&a, &b, &c, &Point::new(0.5, 0.5)));
+    assert!(!Point::in_circle(&a, &b, &c, &Point::new(2.0, 2.0)));
+    assert!(!Point::in_circle(&a, &b, &c, &Point::new(0.0, 1.0)));
+
+    let inside = Point::new(0.0f32, 1.0 - f32::EPSILON);
+    let outside = Point::new(0.0f32, 1.0 + f32::EPSILON);
+    assert!(Point::in_circle(&a, &b, &c, &inside));
+    assert!(!Point::in_circle(&a, &b, &c, &outside));
+}
+
+#[test]
+fn near_collinear_circumcenter() {
+    // Almost on a line: f64 lands some 4e7 away from the exact centre, computed here from
+    // the exact binary values of the coordinates.
+    let (a, b, c) = (
+        Point::new(0.1, 0.1),
+        Point::new(0.2, 0.2),
+        Point::new(0.3, 0.3 + 1e-13),
+    );
+    let exact = Point::new(-200048845191.3601, 200048845191.66013);
+    let error = |p: Point| (p.x - exact.x).hypot(p.y - exact.y) / exact.x.abs();
+
+    assert!(error(Point::circumcenter(&a, &b, &c)) > 1e-5);
+    let center = Point::robust_circumcenter(&a, &b, &c).unwrap();
+    assert!(error(center) < 1e-15, "{:?}", center);
+
+    let d = Point::new(0.4, 0.4);
+    assert!(
+        Point::robust_circumcenter(&Point::new(0.0, 0.0), &Point::new(0.25, 0.25), &d).is_none()
+    );
+}
@@ -0,0 +1,111 @@
+use graph::{Bounds, Point, PoissonDisk};
+use rand::{rngs::StdRng, SeedableRng};
+
+// Distance between the closest two points, by brute force.
+fn closest(points: &[Point]) -> f64 {
+    let mut closest = f64::INFINITY;
+    for (i, p) in points.iter().enumerate() {
+        for q in &points[i + 1..] {
+            closest = closest.min((p.x - q.x).hypot(p.y - q.y));
+        }
+    }
+    closest
+}
+
+// An L covering three quarters of the square from (1, 1) to (3, 3).
+fn l_shape() -> Bounds {
+    Bounds::Polygon(vec![
+        Point::new(1.0, 1.0),
+        Point::new(3.0, 1.0),
+        Point::new(3.0, 2.0),
+        Point::new(2.0, 2.0),
+        Point::new(2.0, 3.0),
+        Point::new(1.0, 3.0),
+    ])
+}
+
+#[test]
+fn variable_density_fills_both_halves() {
+    for &seed in &[2, 3, 5] {
+        let points = PoissonDisk::new(0.02)
+            .with_density(|point| if point.x < 0.5 { 1.0 } else { 0.25 })
+            .sample(&mut StdRng::seed_from_u64(seed));
+
+        let left = points.iter().filter(|point| point.x < 0.5).count();
+        let right = points.len() - left;
+        assert!(left > 500, "seed {}: {} points on the left", seed, left);
+        assert!(right > 150, "seed {}: {} points on the right", seed, right);
+    }
+}
+
+#[test]
+fn minimum_distance() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let points = PoissonDisk::new(0.03).sample(&mut rng);
+    assert!(points.len() > 500, "{} points", points.len());
+    assert!(closest(&points) >= 0.03);
+
+    // Dense spots are clamped to 1 and never pack points closer than the minimum.
+    let points = PoissonDisk::new(0.03)
+        .with_density(|point| 4.0 * point.x)
+        .sample(&mut rng);
+    assert!(points.len() > 200, "{} points", points.len());
+    assert!(closest(&points) >= 0.03);
+
+    let points = PoissonDisk::new(0.05)
+        .with_bounds(l_shape())
+        .with_density(|point| 1.0 / point.y)
+        .sample(&mut rng);
+    assert!(points.len() > 200, "{} points", points.len());
+    assert!(closest(&points) >= 0.05);
+}
+
+#[test]
+fn stays_inside_bounds() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let rect = Bounds::Rect(Point::new(2.0, -1.0), Point::new(-1.0, 0.5));
+    let points = PoissonDisk::new(0.05)
+        .with_bounds(rect.clone())
+        .sample(&mut rng);
+    assert!(points.len() > 500, "{} points", points.len());
+    for point in &points {
+        assert!(point.x >= -1.0 && point.x <= 2.0, "{:?}", point);
+        assert!(point.y >= -1.0 && point.y <= 0.5, "{:?}", point);
+        assert!(rect.contains(point), "{:?}", point);
+    }
+
+    let bounds = l_shape();
+    let points = PoissonDisk::new(0.05)
+        .with_bounds(bounds.clone())
+        .sample(&mut rng);
+    assert!(points.len() > 500, "{} points", points.len());
+    for point in &points {
+        assert!(bounds.contains(point), "{:?}", point);
+        assert!(point.x < 2.0 || point.y < 2.0, "{:?} in the notch", point);
+    }
+    // Every quarter of the L gets its share.
+    for &(x, y) in &[(1.0, 1.0), (2.0, 1.0), (1.0, 2.0)] {
+        let quarter = points
+            .iter()
+            .filter(|p| p.x >= x && p.x < x + 1.0 && p.y >= y && p.y < y + 1.0)
+            .count();
+        assert!(
+            quarter * 4 > points.len(),
+            "{} points by ({}, {})",
+            quarter,
+            x,
+            y
+        );
+    }
+}
+
+#[test]
+fn seeded() {
+    let sampler = PoissonDisk::new(0.05).with_density(|point| 0.5 + point.y);
+    let sample = |seed| {
+        let points = sampler.sample(&mut StdRng::seed_from_u64(seed));
+        points.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>()
+    };
+    assert_eq!(sample(7), sample(7));
+    assert_ne!(sample(7), sample(8));
+}
@@ -0,0 +1,90 @@
+use graph::{Delaunay, Histogram, Point};
+
+mod common;
+
+use common::close;
+
+// A 2 by 2 square around a centre point: four right isosceles triangles with legs of
+// length sqrt(2) and an area of 1.
+fn square() -> Delaunay {
+    Delaunay::from(vec![
+        Point::new(0.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(2.0, 2.0),
+        Point::new(0.0, 2.0),
+        Point::new(1.0, 1.0),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn square_report() {
+    let report = square().quality_report();
+    assert_eq!(report.triangles, 4);
+    assert_eq!(report.slivers, 0);
+
+    // Eight angles of 45 degrees and four of 90.
+    let angles = &report.angles;
+    assert_eq!(angles.count, 12);
+    assert!(close(angles.min, 45.0) && close(angles.max, 90.0) && close(angles.mean, 60.0));
+    assert!(close(angles.std_dev, 450f64.sqrt()));
+    assert_eq!(angles.histogram.bounds.len(), 13);
+    assert_eq!(
+        angles.histogram.counts,
+        vec![0, 0, 0, 8, 0, 0, 4, 0, 0, 0, 0, 0]
+    );
+
+    // Circumradius 1 over twice the inradius sqrt(2) - 1.
+    let aspect = (2f64.sqrt() + 1.0) / 2.0;
+    let aspect_ratios = &report.aspect_ratios;
+    assert_eq!(aspect_ratios.count, 4);
+    assert!(close(aspect_ratios.min, aspect) && close(aspect_ratios.max, aspect));
+    assert!(close(aspect_ratios.std_dev, 0.0));
+    assert_eq!(aspect_ratios.histogram.counts, vec![4, 0, 0, 0, 0, 0]);
+
+    // Equal areas fall in a single bin.
+    let areas = &report.areas;
+    assert!(close(areas.min, 1.0) && close(areas.max, 1.0) && close(areas.mean, 1.0));
+    assert_eq!(
+        areas.histogram,
+        Histogram {
+            bounds: vec![1.0, 1.0],
+            counts: vec![4],
+        }
+    );
+
+    // Four sides of 2 and four spokes of sqrt(2), in the outer bins.
+    let lengths = &report.edge_lengths;
+    assert_eq!(lengths.count, 8);
+    assert!(close(lengths.min, 2f64.sqrt()) && close(lengths.max, 2.0));
+    assert!(close(lengths.mean, 1.0 + 2f64.sqrt() / 2.0));
+    assert_eq!(lengths.histogram.counts, vec![4, 0, 0, 0, 0, 0, 0, 0, 0, 4]);
+}
+
+#[test]
+fn flat_triangles_are_slivers() {
+    // The collinear bottom row stacks a flat triangle under the two real ones.
+    let report = Delaunay::from(vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(1.0, 1.0),
+    ])
+    .unwrap()
+    .quality_report();
+
+    assert_eq!(report.triangles, 3);
+    assert_eq!(report.slivers, 1);
+    assert_eq!(report.aspect_ratios.count, 2);
+    assert!(close(report.areas.min, 0.0) && close(report.areas.max, 0.5));
+    assert!(close(report.angles.min, 0.0) && close(report.angles.max, 180.0));
+}
+
+#[test]
+fn display() {
+    let text = format!("{:.2}", square().quality_report());
+    assert!(text.starts_with("triangles: 4\nangles: min 45.00, max 90.00, mean 60.00"));
+    assert!(text.contains("  [45.00, 60.00) 8\n"));
+    assert!(text.contains("  [165.00, 180.00] 0\n"));
+    assert!(text.ends_with("slivers (angle < 10°): 0\n"));
+}
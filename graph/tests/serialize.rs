@@ -0,0 +1,128 @@
+#![cfg(feature = "serde")]
+
+use graph::triangle::{read_ele, read_node};
+use graph::{Bounds, Delaunay, Graph, Point, VertexId, Voronoi};
+
+mod common;
+
+#[test]
+fn round_trip() {
+    let mut delaunay = common::delaunay(50);
+    let heights = (0..delaunay.vertex_count())
+        .map(|i| i as f64 / 2.0)
+        .collect();
+    delaunay
+        .vertex_attributes_mut()
+        .insert("height", heights)
+        .unwrap();
+
+    let json = serde_json::to_string(&delaunay).unwrap();
+    let read: Delaunay = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.vertex_count(), delaunay.vertex_count());
+    assert_eq!(read.face_count(), delaunay.face_count());
+    assert_eq!(
+        read.vertex_attributes().get::<f64>("height"),
+        delaunay.vertex_attributes().get::<f64>("height")
+    );
+    for (read, written) in read.edges().zip(delaunay.edges()) {
+        assert_eq!(read.origin().id(), written.origin().id());
+        assert_eq!(read.left_face().id(), written.left_face().id());
+    }
+
+    let clipped = Voronoi::from(&delaunay).clipped(&Bounds::unit()).unwrap();
+    let json = serde_json::to_string(&clipped).unwrap();
+    let read: Graph<()> = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        read.face_attributes().get::<VertexId>("site"),
+        clipped.face_attributes().get::<VertexId>("site")
+    );
+}
+
+#[test]
+fn unknown_attribute_type() {
+    #[derive(Clone, Debug, Default)]
+    struct Label;
+
+    let mut delaunay = common::delaunay(50);
+    delaunay.face_attributes_mut().add::<Label>("label");
+    assert!(serde_json::to_string(&delaunay).is_err());
+}
+
+#[test]
+fn broken_input() {
+    let mut delaunay = common::delaunay(50);
+    delaunay.edge_attributes_mut().add::<u32>("weight");
+    let json = serde_json::to_string(&delaunay).unwrap();
+
+    let short = json.replacen("\"U32\":[0,", "\"U32\":[", 1);
+    assert!(serde_json::from_str::<Delaunay>(&short).is_err());
+
+    let broken = json.replacen("\"next\":", "\"next\":7", 1);
+    assert!(serde_json::from_str::<Delaunay>(&broken).is_err());
+}
+
+#[test]
+fn flat_triangle_inside() {
+    // Moving (1, -0.5) onto the segment between (0, 0) and (2, 0) flattens its triangle
+    // against the one above.
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, -0.5),
+        Point::new(2.0, 0.0),
+        Point::new(1.0, 5.0),
+        Point::new(1.0, -3.0),
+    ];
+    let delaunay: Delaunay = Delaunay::from(points).unwrap();
+    let json = serde_json::to_string(&delaunay).unwrap();
+    assert!(serde_json::from_str::<Delaunay>(&json).is_ok());
+
+    let flat = json.replacen("-0.5", "0.0", 1);
+    let error = serde_json::from_str::<Delaunay>(&flat).unwrap_err();
+    assert!(error.to_string().contains("flat"), "{}", error);
+}
+
+#[test]
+fn not_a_triangulation() {
+    // Clipped cells are polygons.
+    let clipped = Voronoi::from(&common::delaunay(50))
+        .clipped(&Bounds::unit())
+        .unwrap();
+    let json = serde_json::to_string(&clipped).unwrap();
+    let error = serde_json::from_str::<Delaunay>(&json).unwrap_err();
+    assert!(error.to_string().contains("not a triangle"), "{}", error);
+
+    // A ring of triangles around a triangular hole, which gets an infinite vertex of its own.
+    let node = "6 2 0 0\n1 0 0\n2 10 0\n3 5 10\n4 4 2\n5 6 2\n6 5 4\n";
+    let ele = "6 3 0\n1 1 2 5\n2 1 5 4\n3 2 3 6\n4 2 6 5\n5 3 1 4\n6 3 4 6\n";
+    let nodes = read_node::<_, f64>(node.as_bytes()).unwrap();
+    let ring = read_ele(ele.as_bytes(), &nodes).unwrap();
+    let json = serde_json::to_string(&ring).unwrap();
+    assert!(serde_json::from_str::<Graph<()>>(&json).is_ok());
+    let error = serde_json::from_str::<Delaunay>(&json).unwrap_err();
+    assert!(error.to_string().contains("infinite vertex"), "{}", error);
+}
+
+#[test]
+fn voronoi_rays() {
+    let voronoi = Voronoi::from(&common::delaunay(50));
+    let json = serde_json::to_value(&voronoi).unwrap();
+    let read: Voronoi = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(read.vertex_count(), voronoi.vertex_count());
+
+    let ray = json["vertices"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .position(|vertex| vertex["position"].get("Right").is_some())
+        .unwrap();
+    let broken = |field: &str, value: serde_json::Value| {
+        let mut json = json.clone();
+        json["vertices"][ray]["position"]["Right"][field] = value;
+        let error = serde_json::from_value::<Voronoi>(json).unwrap_err();
+        assert!(error.to_string().contains("ray"), "{}", error);
+    };
+    broken("direction", serde_json::json!([1.0, 1.0]));
+    broken("length", serde_json::json!(0.0));
+    broken("sites", serde_json::json!([1, 1]));
+    broken("sites", serde_json::json!([0, voronoi.face_count() + 1]));
+}
@@ -0,0 +1,108 @@
+use graph::triangle::{read_ele, read_neigh, read_node, write_ele, write_neigh, write_node};
+use graph::{Delaunay, Point};
+
+mod common;
+
+#[test]
+fn round_trip() {
+    let delaunay = common::delaunay(100);
+
+    let (mut node, mut ele, mut neigh) = (Vec::new(), Vec::new(), Vec::new());
+    write_node(&delaunay, &mut node).unwrap();
+    write_ele(&delaunay, &mut ele).unwrap();
+    write_neigh(&delaunay, &mut neigh).unwrap();
+
+    let nodes = read_node::<_, f64>(&node[..]).unwrap();
+    assert_eq!(nodes.first, 1);
+    let graph = read_ele(&ele[..], &nodes).unwrap();
+    assert_eq!(graph.validate(), Ok(()));
+    assert_eq!(graph.vertex_count(), delaunay.vertex_count());
+    assert_eq!(graph.face_count(), delaunay.face_count());
+
+    let triangles = delaunay.faces().filter(|face| !face.is_infinite()).count();
+    let neighbors = read_neigh(&neigh[..], &nodes).unwrap();
+    assert_eq!(neighbors.len(), triangles);
+    let hull = neighbors.iter().flatten().filter(|n| n.is_none()).count();
+    let infinite = delaunay.faces().filter(|face| face.is_infinite()).count();
+    assert_eq!(hull, infinite);
+}
+
+#[test]
+fn grid_round_trip() {
+    // The hull of a 5 by 5 grid keeps 12 flat triangles along its sides, which are not written.
+    let points = (0..25)
+        .map(|i| Point::new((i % 5) as f64, (i / 5) as f64))
+        .collect::<Vec<_>>();
+    let delaunay = Delaunay::from(points).unwrap();
+
+    let (mut node, mut ele, mut neigh) = (Vec::new(), Vec::new(), Vec::new());
+    write_node(&delaunay, &mut node).unwrap();
+    write_ele(&delaunay, &mut ele).unwrap();
+    write_neigh(&delaunay, &mut neigh).unwrap();
+
+    let nodes = read_node::<_, f64>(&node[..]).unwrap();
+    let graph = read_ele(&ele[..], &nodes).unwrap();
+    assert_eq!(graph.validate(), Ok(()));
+    let finite = graph.faces().filter(|face| !face.is_infinite()).count();
+    assert_eq!(finite, 32);
+    let area = graph.faces().filter_map(|face| face.area()).sum::<f64>();
+    assert!((area - 16.0).abs() < 1e-12);
+
+    let neighbors = read_neigh(&neigh[..], &nodes).unwrap();
+    assert_eq!(neighbors.len(), 32);
+    let hull = neighbors.iter().flatten().filter(|n| n.is_none()).count();
+    assert_eq!(hull, 16);
+}
+
+#[test]
+fn hole_and_unused_nodes() {
+    // A 4 by 4 grid of nodes without the middle cell, and one node no triangle uses.
+    let mut node = String::from("17 2 0 1\n");
+    for i in 0..16 {
+        node += &format!("{} {} {} {}\n", i + 1, i % 4, i / 4, i);
+    }
+    node += "17 10 10 16\n";
+
+    let mut triangles = Vec::new();
+    for row in 0..3 {
+        for column in 0..3 {
+            if (row, column) == (1, 1) {
+                continue;
+            }
+            let corner = row * 4 + column + 1;
+            triangles.push([corner, corner + 1, corner + 5]);
+            triangles.push([corner, corner + 5, corner + 4]);
+        }
+    }
+    let mut ele = format!("{} 3 0\n", triangles.len());
+    for (i, [a, b, c]) in triangles.iter().enumerate() {
+        ele += &format!("{} {} {} {}\n", i + 1, a, b, c);
+    }
+
+    let nodes = read_node::<_, f64>(node.as_bytes()).unwrap();
+    let graph = read_ele(ele.as_bytes(), &nodes).unwrap();
+    assert_eq!(graph.validate(), Ok(()));
+
+    // The infinite vertex, the 16 used nodes and the infinite vertex of the hole.
+    assert_eq!(graph.vertex_count(), 18);
+    let finite = graph.faces().filter(|face| !face.is_infinite()).count();
+    assert_eq!(finite, 16);
+    let area = graph.faces().filter_map(|face| face.area()).sum::<f64>();
+    assert!((area - 8.0).abs() < 1e-12);
+
+    let markers = graph.vertex_attributes().get::<i32>("marker").unwrap();
+    assert_eq!(markers[1..17], (0..16).collect::<Vec<_>>()[..]);
+    assert_eq!(markers[17], 0);
+}
+
+#[test]
+fn degenerate_triangles() {
+    let node = "4 2 0 0\n1 0 0\n2 1 0\n3 0 1\n4 2 0\n";
+    let nodes = read_node::<_, f64>(node.as_bytes()).unwrap();
+
+    for ele in &["1 3 0\n1 1 2 2\n", "1 3 0\n1 3 1 3\n", "1 3 0\n1 1 2 4\n"] {
+        let error = read_ele(ele.as_bytes(), &nodes).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData, "{:?}", ele);
+    }
+    assert!(read_ele("1 3 0\n1 1 2 3\n".as_bytes(), &nodes).is_ok());
+}
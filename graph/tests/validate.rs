@@ -0,0 +1,83 @@
+use graph::distributions::{Halton, NearCollinear};
+use graph::{Delaunay, Point, Voronoi};
+use rand::distributions::Distribution;
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn halton() {
+    let points: Vec<Point> = Halton::new().take(2000).collect();
+    let delaunay = Delaunay::from(points).unwrap();
+
+    assert_eq!(delaunay.validate(), Ok(()));
+    assert_eq!(delaunay.verify(), Ok(()));
+    assert_eq!(Voronoi::from(&delaunay).validate(), Ok(()));
+}
+
+#[test]
+fn cocircular() {
+    let points = (0..100)
+        .map(|i| Point::new(0.013 + (i % 10) as f64 * 0.1, 0.027 + (i / 10) as f64 * 0.1))
+        .collect();
+    let delaunay = Delaunay::from(points).unwrap();
+
+    assert_eq!(delaunay.validate(), Ok(()));
+    assert_eq!(delaunay.verify(), Ok(()));
+}
+
+#[test]
+fn collinear_runs() {
+    let inputs = [
+        vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 0.0),
+            (2.0, 1.0),
+        ],
+        vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 0.0),
+            (2.0, -1.0),
+        ],
+        vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 0.0),
+            (0.5, 3.0),
+        ],
+    ];
+    for input in &inputs {
+        let points = input.iter().map(|&(x, y)| Point::new(x, y)).collect();
+        let delaunay = Delaunay::from(points).unwrap();
+
+        assert_eq!(delaunay.validate(), Ok(()), "{:?}", input);
+        assert_eq!(delaunay.verify(), Ok(()), "{:?}", input);
+    }
+}
+
+#[test]
+fn single_precision() {
+    let grid = (0..100)
+        .map(|i| Point::new(0.013 + (i % 10) as f32 * 0.1, 0.027 + (i / 10) as f32 * 0.1))
+        .collect::<Vec<_>>();
+    let mut rng = StdRng::seed_from_u64(0);
+    let line = NearCollinear::new(Point::new(0.0f32, 0.0), Point::new(1.0, 0.7), 1e-6)
+        .sample_iter(&mut rng)
+        .take(500)
+        .chain(Some(Point::new(0.5, 0.5)))
+        .collect::<Vec<_>>();
+    let halton = Halton::<f32>::new().take(2000).collect::<Vec<_>>();
+
+    for points in [grid, line, halton] {
+        let delaunay = Delaunay::from(points).unwrap();
+        assert_eq!(delaunay.validate(), Ok(()));
+        assert_eq!(delaunay.verify(), Ok(()));
+        assert_eq!(Voronoi::from(&delaunay).validate(), Ok(()));
+    }
+}
@@ -0,0 +1,171 @@
+use graph::{Delaunay, EdgeId, Point, Ray, Voronoi};
+use std::collections::BTreeSet;
+
+fn grid(n: usize) -> Delaunay {
+    let points = (0..n * n)
+        .map(|i| Point::new((i % n) as f64, (i / n) as f64))
+        .collect();
+    Delaunay::from(points).unwrap()
+}
+
+// Every Voronoi edge separates the two ends of its Delaunay edge, and the corners of the
+// Delaunay triangle of every finite Voronoi vertex are among the cells around it. The other
+// way round, every Delaunay triangle maps to a vertex surrounded by the cells of its corners,
+// and every Delaunay edge to the Voronoi edge mapped back onto it, unless it was contracted.
+// Returns the number of contracted edges.
+fn check_mappings(delaunay: &Delaunay, voronoi: &Voronoi) -> usize {
+    for edge in voronoi.edges() {
+        let cells = [edge.left_face(), edge.right_face()]
+            .iter()
+            .map(|face| voronoi.delaunay_vertex(face.id()))
+            .collect::<BTreeSet<_>>();
+        let (v0, v1) = delaunay.edge(voronoi.delaunay_edge(edge.id())).vertices();
+        let sites = [v0.id(), v1.id()].iter().copied().collect();
+        assert_eq!(cells, sites, "{:?}", edge.id());
+    }
+
+    for vertex in voronoi.vertices().filter(|vertex| !vertex.is_infinite()) {
+        let cells = vertex
+            .faces()
+            .map(|face| voronoi.delaunay_vertex(face.id()))
+            .collect::<BTreeSet<_>>();
+        let face = delaunay.face(voronoi.delaunay_face(vertex.id()));
+        assert!(
+            face.vertices().all(|corner| cells.contains(&corner.id())),
+            "{:?}",
+            vertex.id()
+        );
+    }
+
+    for face in delaunay.faces() {
+        let vertex = voronoi.vertex(delaunay.voronoi_vertex(face.id(), voronoi).unwrap());
+        let cells = vertex
+            .faces()
+            .map(|cell| voronoi.delaunay_vertex(cell.id()))
+            .collect::<BTreeSet<_>>();
+        assert!(
+            face.vertices().all(|corner| cells.contains(&corner.id())),
+            "{:?}",
+            face.id()
+        );
+    }
+
+    let mut contracted = 0;
+    for edge in delaunay.edges() {
+        match delaunay.voronoi_edge(edge.id(), voronoi) {
+            Some(bisector) => assert_eq!(voronoi.delaunay_edge(bisector), edge.id()),
+            None => contracted += 1,
+        }
+    }
+    contracted
+}
+
+#[test]
+fn mappings() {
+    let delaunay = grid(10);
+    let voronoi = Voronoi::from(&delaunay);
+    assert_eq!(check_mappings(&delaunay, &voronoi), 0);
+}
+
+#[test]
+fn merged_mappings() {
+    let delaunay = grid(10);
+    let mut voronoi = Voronoi::from(&delaunay);
+    let edges = voronoi.edge_count();
+
+    let merged = voronoi.merge_coincident_vertices(1e-9);
+    assert_eq!(merged, 81);
+    assert_eq!(voronoi.edge_count(), edges - merged);
+    assert_eq!(check_mappings(&delaunay, &voronoi), merged);
+
+    assert_eq!(voronoi.merge_coincident_vertices(1e-9), 0);
+    assert_eq!(check_mappings(&delaunay, &voronoi), merged);
+
+    // Both triangles of a grid cell map to the one vertex at its center, whatever ids the
+    // merge shuffled.
+    let center = Point::new(4.5, 4.5);
+    let cell = delaunay
+        .faces()
+        .filter(|face| {
+            face.centroid().is_some_and(|centroid| {
+                (centroid.x - center.x).abs() < 0.5 && (centroid.y - center.y).abs() < 0.5
+            })
+        })
+        .map(|face| delaunay.voronoi_vertex(face.id(), &voronoi).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(cell.len(), 2);
+    assert_eq!(cell[0], cell[1]);
+    let position = voronoi.vertex(cell[0]).position().left().unwrap();
+    assert!((position.x - center.x).abs() < 1e-9 && (position.y - center.y).abs() < 1e-9);
+}
+
+#[test]
+fn rays() {
+    // A right triangle: its circumcenter is the midpoint (1, 1) of the hypotenuse, and one
+    // ray leaves it across each side.
+    let delaunay = Delaunay::from(vec![
+        Point::new(0.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(0.0, 2.0),
+    ])
+    .unwrap();
+    let voronoi = Voronoi::from(&delaunay);
+
+    let rays = voronoi
+        .vertices()
+        .filter_map(|vertex| vertex.position().right())
+        .collect::<Vec<Ray>>();
+    assert_eq!(rays.len(), 3);
+
+    let mut found = Vec::new();
+    for ray in &rays {
+        let (v0, v1) = ray.sites();
+        let p0 = delaunay.vertex(v0).position().left().unwrap();
+        let p1 = delaunay.vertex(v1).position().left().unwrap();
+        let (dx, dy) = (p1.x - p0.x, p1.y - p0.y);
+
+        assert_eq!((ray.origin().x, ray.origin().y), (1.0, 1.0));
+        assert!((ray.length() - dx.hypot(dy)).abs() < 1e-12);
+
+        // A unit vector at right angles to the hull edge, turned away from the triangle.
+        let direction = ray.direction();
+        assert!((direction.x.hypot(direction.y) - 1.0).abs() < 1e-12);
+        assert!((direction.x * dx + direction.y * dy).abs() < 1e-12);
+        assert!(direction.x * (2.0 / 3.0 - p0.x) + direction.y * (2.0 / 3.0 - p0.y) < 0.0);
+        let far = ray.at(10.0);
+        assert!((far.x - 1.0 - 10.0 * direction.x).abs() < 1e-12);
+        assert!((far.y - 1.0 - 10.0 * direction.y).abs() < 1e-12);
+
+        found.push((
+            (direction.x * 2f64.sqrt()).round(),
+            (direction.y * 2f64.sqrt()).round(),
+        ));
+    }
+    found.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(found, vec![(-1.0, 0.0), (0.0, -1.0), (1.0, 1.0)]);
+}
+
+#[test]
+#[should_panic(expected = "attribute `delaunay_edge` is reserved")]
+fn reserved_delaunay_attribute() {
+    let mut delaunay = grid(4);
+    let count = delaunay.edge_count();
+    delaunay
+        .edge_attributes_mut()
+        .insert("delaunay_edge", vec![EdgeId::new(0); count])
+        .unwrap();
+    let _ = Voronoi::from(&delaunay);
+}
+
+#[test]
+#[should_panic(expected = "attribute `delaunay_face` is reserved")]
+fn reserved_voronoi_attribute() {
+    let delaunay = grid(4);
+    let mut voronoi = Voronoi::from(&delaunay);
+    let count = voronoi.vertex_count();
+    voronoi
+        .vertex_attributes_mut()
+        .insert("delaunay_face", vec![0u32; count])
+        .unwrap();
+    voronoi.merge_coincident_vertices(1e-9);
+}
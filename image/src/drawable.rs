@@ -1,6 +1,6 @@
 use crate::{Rgb, RgbImage};
 use either::{Left, Right};
-use graph::{Edge, Face, Graph, Vector, Vertex};
+use graph::{Edge, Face, Graph, Point, Ray, Vertex};
 use imageproc::{drawing, point};
 use std::fmt::Debug;
 
@@ -39,7 +39,7 @@ impl Drawable for Edge<'_, ()> {
     }
 }
 
-impl Drawable for Edge<'_, Vector> {
+impl Drawable for Edge<'_, Ray> {
     fn draw(&self, image: &mut RgbImage) {
         let black = Rgb([0u8, 0u8, 0u8]);
         let red = Rgb([255u8, 0u8, 0u8]);
@@ -56,8 +56,9 @@ impl Drawable for Edge<'_, Vector> {
                     black,
                 );
             }
-            (Left(p1), Right(v)) => {
-                let p2 = *p1 + *v;
+            (Left(p1), Right(ray)) | (Right(ray), Left(p1)) => {
+                let (d, length) = (ray.direction(), ray.length());
+                let p2 = Point::new(p1.x + d.x * length, p1.y + d.y * length);
 
                 drawing::draw_line_segment_mut(
                     image,
@@ -98,7 +99,7 @@ impl Drawable for Face<'_, ()> {
     }
 }
 
-impl Drawable for Face<'_, Vector> {
+impl Drawable for Face<'_, Ray> {
     fn draw(&self, _image: &mut RgbImage) {}
 }
 
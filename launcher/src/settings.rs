@@ -1,9 +1,13 @@
+use std::path::{Path, PathBuf};
+
 #[derive(Debug, serde_derive::Deserialize)]
 pub struct Settings {
     #[serde(default = "default_number")]
     number: u64,
     #[serde(default = "default_seed")]
     seed: u64,
+    #[serde(default)]
+    input: Option<PathBuf>,
 }
 
 const fn default_number() -> u64 {
@@ -21,4 +25,7 @@ impl application::Settings for Settings {
     fn number(&self) -> u64 {
         self.number
     }
+    fn input(&self) -> Option<&Path> {
+        self.input.as_deref()
+    }
 }